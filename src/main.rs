@@ -11,15 +11,107 @@ use std::collections::{HashMap, VecDeque};
 const LARGURA_TRELICA: Decimal = dec!(0.37);
 const LARGURA_ISOPOR: Decimal = dec!(0.42);
 const COMPRIMENTO_STEP: Decimal = dec!(0.2);
+const LARGURA_CHAPA_COMPENSADO: Decimal = dec!(1.10);
+const COMPRIMENTO_CHAPA_COMPENSADO: Decimal = dec!(2.20);
+const ESPACAMENTO_SARRAFO_FORMA: Decimal = dec!(0.5);
+const PESO_ESPECIFICO_CONCRETO: Decimal = dec!(25); // kN/m³
+const LARGURA_NERVURA_VIGOTA: Decimal = dec!(0.08); // concreto entre blocos, por viga (m)
+const SECAO_SAPATA_VIGOTA: Decimal = dec!(0.0036); // base pré-moldada da vigota, 12 x 3 cm (m²)
+const KN_POR_KG: Decimal = dec!(0.00981);
 const PESO_AREIA_POR_M3: Decimal = dec!(1450);
 const PESO_SACO_AREIA: Decimal = dec!(20);
 const PESO_SACO_CIMENTO: Decimal = dec!(50);
+const COMPRIMENTO_BARRA_ACO: Decimal = dec!(12);
+const FATOR_MASSA_LINEAR_ACO: Decimal = dec!(0.006165); // kg/m por mm² de diâmetro (π/4 x 7850 / 10⁶)
+const TAMANHO_MINIMO_CORTE_PISO: Decimal = dec!(0.01); // frestas menores ficam para o rodapé (m)
+const TAMANHO_MINIMO_SOBRA_PISO: Decimal = dec!(0.05); // menor retalho que ainda vale guardar (m)
+const SENO_45: Decimal = dec!(0.7071067811865475);
+const ESPESSURA_CORTE_RODAPE: Decimal = dec!(3); // disco de corte entre faixas (mm)
+const PERDA_RODAPE: Decimal = dec!(1.10); // emendas e cantos
+const CONSUMO_ARGAMASSA_RODAPE: Decimal = dec!(5); // kg/m²
+const ESPACAMENTO_PREGOS_RODAPE: Decimal = dec!(0.40);
+const RENDIMENTO_CARTUCHO_COLA: Decimal = dec!(10); // metros de rodapé por cartucho
+const FATOR_VOLUME_SECO_ARGAMASSA: Decimal = dec!(1.25); // materiais secos por m³ de argamassa pronta
+const EMBALAGENS_REJUNTE: [Decimal; 3] = [dec!(1), dec!(5), dec!(20)]; // kg
+const LADO_MAXIMO_COLAGEM_SIMPLES: Decimal = dec!(600); // porcelanato acima de 60x60 (mm)
+const AREA_MAXIMA_COLAGEM_SIMPLES_EXTERNA: Decimal = dec!(90000); // 30x30 cm em mm²
+const LADO_MINIMO_NIVELADOR: Decimal = dec!(600); // a partir daqui usa clipes niveladores (mm)
+const ESPACAMENTO_CLIPES_NIVELADOR: Decimal = dec!(400); // distância máxima entre clipes (mm)
+const REUSOS_CUNHA_NIVELADOR: Decimal = dec!(4); // cunhas são reaproveitadas nas fiadas seguintes
+const PERDA_ESPACADORES: Decimal = dec!(1.10);
+const EMBALAGENS_ESPACADORES: [Decimal; 2] = [dec!(100), dec!(500)]; // unidades
+const FOLGA_DILATACAO_LAMINADO: Decimal = dec!(10); // junto a cada parede, coberta pelo rodapé (mm)
+const PASSO_CORTE_LAMINADO: Decimal = dec!(10); // ajuste da peça de início das fileiras (mm)
+const PERDA_MANTA_LAMINADO: Decimal = dec!(1.05);
+const LIMITE_PECAS_DESENHO: Decimal = dec!(5000); // acima disso a visualização fica lenta
+const LIMITE_PECAS_PAGINACAO: Decimal = dec!(20000); // por ambiente; acima disso o cálculo trava a tela
+
+// Vãos máximos de referência (m) para vigotas simplesmente apoiadas, com escoramento
+// durante a concretagem. Confirmar sempre com a tabela do fabricante.
+const MODELOS_TRELICA: [ModeloTrelica; 5] = [
+    ModeloTrelica { nome: "TR8644", altura_laje: 12, vao_cobertura: dec!(4.5), vao_residencial: dec!(3.8), vao_comercial: dec!(3.2) },
+    ModeloTrelica { nome: "TR12645", altura_laje: 16, vao_cobertura: dec!(5.6), vao_residencial: dec!(4.8), vao_comercial: dec!(4.2) },
+    ModeloTrelica { nome: "TR16745", altura_laje: 20, vao_cobertura: dec!(6.6), vao_residencial: dec!(5.8), vao_comercial: dec!(5.2) },
+    ModeloTrelica { nome: "TR20745", altura_laje: 25, vao_cobertura: dec!(7.6), vao_residencial: dec!(6.8), vao_comercial: dec!(6.1) },
+    ModeloTrelica { nome: "TR25856", altura_laje: 30, vao_cobertura: dec!(8.5), vao_residencial: dec!(7.8), vao_comercial: dec!(7.0) },
+];
+
+// Fôrmas plásticas para laje nervurada. O volume vazio é aproximado pelo tronco de
+// pirâmide da fôrma; conferir no catálogo do fornecedor.
+const CUBETAS_NERVURADA: [Cubeta; 4] = [
+    Cubeta { nome: "Cubeta 61x61 H16", modulo: dec!(0.61), altura: dec!(0.16), volume_vazio: dec!(0.0425) },
+    Cubeta { nome: "Cubeta 61x61 H21", modulo: dec!(0.61), altura: dec!(0.21), volume_vazio: dec!(0.0537) },
+    Cubeta { nome: "Cubeta 61x61 H26", modulo: dec!(0.61), altura: dec!(0.26), volume_vazio: dec!(0.0637) },
+    Cubeta { nome: "Cubeta 80x80 H26", modulo: dec!(0.80), altura: dec!(0.26), volume_vazio: dec!(0.1222) },
+];
+
+// Blocos de enchimento disponíveis para cada tipo de laje
+const BLOCOS_LAJE: [BlocoLaje; 6] = [
+    BlocoLaje { nome: "Lajota Cerâmica H7 (30x20 cm)", tipo: TipoLaje::Trelica, altura: 7, peso: dec!(2.2) },
+    BlocoLaje { nome: "Lajota Cerâmica H8 (30x20 cm)", tipo: TipoLaje::Trelica, altura: 8, peso: dec!(2.6) },
+    BlocoLaje { nome: "Lajota Cerâmica H12 (30x20 cm)", tipo: TipoLaje::Trelica, altura: 12, peso: dec!(4.3) },
+    BlocoLaje { nome: "EPS H7 (peça de 50 cm)", tipo: TipoLaje::Isopor, altura: 7, peso: dec!(0.15) },
+    BlocoLaje { nome: "EPS H8 (peça de 50 cm)", tipo: TipoLaje::Isopor, altura: 8, peso: dec!(0.17) },
+    BlocoLaje { nome: "EPS H12 (peça de 50 cm)", tipo: TipoLaje::Isopor, altura: 12, peso: dec!(0.25) },
+];
 
 #[derive(Serialize, Deserialize, Default)]
 struct Configuracao {
     fator_argamassa_simples: String,
     fator_argamassa_dupla: String,
     coeficiente_rejunte: String,
+    #[serde(default)]
+    produtos_piso: Vec<ProdutoPiso>,
+    #[serde(default)]
+    arquivo_desenho_laje: String,
+}
+
+// Produto do catálogo de revestimentos, salvo na configuração
+#[derive(Serialize, Deserialize, Clone, Default)]
+struct ProdutoPiso {
+    nome: String,
+    largura: String,     // mm
+    comprimento: String, // mm
+    espessura: String,   // mm
+    area_caixa: String,  // m²
+    pecas_caixa: String,
+    peso_caixa: String,  // kg (opcional)
+    preco_caixa: String, // R$ (opcional)
+    #[serde(default)]
+    porcelanato: bool,
+}
+
+// Dados já convertidos do revestimento usado por um grupo de ambientes
+struct RevestimentoPiso {
+    nome: String,
+    largura: Decimal,     // mm
+    comprimento: Decimal, // mm
+    espessura: Decimal,   // mm
+    area_caixa: Decimal,
+    pecas_caixa: u32,
+    peso_caixa: Option<Decimal>,
+    preco_caixa: Option<Decimal>,
+    porcelanato: bool,
 }
 
 struct CalculadoraConstrucao {
@@ -42,17 +134,63 @@ enum Modo {
 }
 
 struct CalculadoraLaje {
-    tipo_laje: TipoLaje,
+    uso_laje: UsoLaje,
     ambientes: Vec<AmbienteLaje>,
     resultado: String,
+    desenhos: Vec<DesenhoLaje>,
+    mensagem_exportacao: String,
+    arquivo_svg: String,
+    confirmar_sobrescrita: Option<String>, // arquivo existente que o próximo clique substitui
+    // Apoio das vigas nas paredes
+    referencia_medida: ReferenciaMedida,
+    espessura_parede: String, // m
+    apoio_viga: String,       // apoio em cada extremidade (m)
+    espessura_capa: String,   // capa de concreto sobre vigas e nervuras (m)
+    // Campos da laje maciça
+    espessura_macica: String,       // m
+    reaproveitamento_forma: String, // número de usos de cada chapa
+    taxa_aco_macica: String,        // kg de aço por m³ de concreto
+    // Campos da laje nervurada
+    cubeta_nervurada: usize, // índice em CUBETAS_NERVURADA
+    // Campos para o cálculo de cargas
+    calcular_cargas: bool,
+    carga_revestimento: String, // kN/m²
+    carga_acidental: String,    // kN/m²
+    // Campos para o cálculo da armadura de distribuição
+    calcular_armadura: bool,
+    tipo_armadura: TipoArmadura,
+    modelo_tela: ModeloTela,
+    painel_tela: PainelTela,
+    transpasse_tela: String,   // transpasse entre painéis (m)
+    diametro_barra: String,    // diâmetro da barra (mm)
+    espacamento_barra: String, // espaçamento entre barras (m)
+    // Campos para o cálculo do escoramento
+    calcular_escoramento: bool,
+    tipo_escora: TipoEscora,
+    espacamento_linhas_escora: String, // distância entre linhas de escoramento (m)
+    espacamento_escoras: String,       // distância entre escoras na mesma linha (m)
+    valor_diaria_escora: String,       // R$ por escora por dia (opcional)
+    dias_locacao: String,
+    // Campos para o plano de corte das vigas
+    calcular_plano_corte: bool,
+    comprimento_estoque_viga: String, // comprimento das barras entregues (m)
+    sobras_vigas: String,             // sobras reaproveitáveis, separadas por ';' (m)
+}
+
+struct BarraCorte {
+    comprimento: Decimal,
+    reaproveitada: bool, // sobra de obra em vez de barra nova
+    cortes: Vec<Decimal>,
 }
 
 struct CalculadoraPiso {
+    modo: ModoRevestimento,
     area_caixa: String,
     ambientes: Vec<Ambiente>,
     calcular_piso: bool,
     calcular_argamassa: bool,
     calcular_rejunte: bool,
+    calcular_espacadores: bool,
     resultado: String,
     // Campos para o cálculo do rejunte
     tile_width: String,      // largura do revestimento (mm)
@@ -60,9 +198,152 @@ struct CalculadoraPiso {
     tile_thickness: String,  // espessura do revestimento (mm)
     joint_spacing: String,   // espaçamento das juntas (mm)
     rejunte_coeficiente: String,
+    precos_rejunte: [String; 3], // R$ por embalagem de EMBALAGENS_REJUNTE (opcional)
     // Campo para o cálculo da argamassa
     application_method: ApplicationMethod,
     argamassa_fator: String,
+    fator_colagem_dupla: String, // usado quando a colagem dupla é exigida pelo revestimento
+    local_assentamento: LocalAssentamento,
+    porcelanato: bool,
+    // Campos para a paginação do piso
+    simular_paginacao: bool,
+    pecas_caixa: String,
+    padrao_assentamento: PadraoAssentamento,
+    inicio_paginacao: InicioPaginacao,
+    desenhos_paginacao: Vec<(ChaveDesenho, Result<Vec<FormaDesenho>, String>)>, // por ambiente
+    // Política de compra das caixas
+    percentual_perda: String,
+    caixas_reserva: String,
+    caixas_palete: String, // opcional; arredonda pedidos a partir de um palete
+    // Campos para o rodapé
+    calcular_rodape: bool,
+    tipo_rodape: TipoRodape,
+    altura_rodape: String,           // altura da faixa cerâmica (cm)
+    comprimento_regua_rodape: String, // comprimento da régua pronta (m)
+    fixacao_rodape: FixacaoRodape,
+    // Campos para o piso laminado/vinílico
+    comprimento_regua: String,  // mm
+    largura_regua: String,      // mm
+    area_caixa_laminado: String, // m²
+    deslocamento_regua: String, // deslocamento mínimo entre fileiras e menor peça (cm)
+    area_rolo_manta: String,    // m² (opcional)
+    comprimento_perfil_transicao: String, // m
+    // Campos para o contrapiso
+    calcular_contrapiso: bool,
+    espessura_contrapiso: String, // espessura média (cm)
+    traco_cimento: String,
+    traco_areia: String,
+    // Campos para a impermeabilização das áreas molhadas
+    calcular_impermeabilizacao: bool,
+    impermeabilizante: Impermeabilizante,
+    consumo_impermeabilizante: String, // por m² e por demão
+    demaos_impermeabilizante: String,
+    altura_subida: String, // subida nas paredes (m)
+    altura_box: String,    // subida nas paredes do box (m)
+    // Catálogo de revestimentos
+    produtos: Vec<ProdutoPiso>,
+    novo_produto: ProdutoPiso,
+    mensagem_catalogo: String,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum ModoRevestimento {
+    Piso,
+    Parede,
+    Laminado, // réguas de piso laminado ou vinílico de encaixe
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum TipoRodape {
+    Ceramico, // faixas cortadas das placas do piso
+    Mdf,      // réguas prontas (MDF, poliestireno)
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum FixacaoRodape {
+    Cola,
+    Pregos,
+}
+
+// Dados que definem o desenho da paginação de um ambiente; o desenho só é refeito
+// quando algum deles muda
+#[derive(PartialEq)]
+struct ChaveDesenho {
+    largura: String,
+    comprimento: String,
+    peca_largura: String,
+    peca_comprimento: String,
+    junta: String,
+    padrao: PadraoAssentamento,
+    inicio: InicioPaginacao,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum InicioPaginacao {
+    Canto,  // primeira peça no canto (0, 0) do ambiente
+    Centro, // primeira peça centralizada, cortes iguais nas paredes opostas
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum LocalAssentamento {
+    Interno,
+    Externo,
+    Piscina,
+    Fachada,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum Impermeabilizante {
+    MantaLiquida,
+    Cimenticia,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum PadraoAssentamento {
+    Reto,
+    AmarracaoMeia,
+    AmarracaoTerco,
+    Diagonal,
+    EspinhaDePeixe,
+}
+
+// Posição de uma peça do padrão: canto de origem e direções do comprimento e da largura
+struct PecaPiso {
+    origem: (Decimal, Decimal),
+    eixo_comprimento: (Decimal, Decimal),
+    eixo_largura: (Decimal, Decimal),
+}
+
+// Trecho de uma peça do padrão que fica dentro do ambiente
+struct RecortePeca {
+    visivel: Vec<(Decimal, Decimal)>,
+    comprimento: Decimal, // retângulo envolvente no sistema da peça
+    largura: Decimal,
+    inteira: bool,
+}
+
+// Corte a tirar de uma peça: retângulo envolvente no sistema da peça
+struct CortePiso {
+    comprimento: Decimal,
+    largura: Decimal,
+    triangular: bool, // meia peça em diagonal, a outra metade serve em outro corte
+}
+
+struct PaginacaoLaminado {
+    fileiras: u32,
+    largura_borda: Decimal, // largura da fileira cortada ao comprido (mm)
+    bordas_equilibradas: bool, // primeira e última fileiras cortadas com a mesma largura
+    reguas: u32,
+    cortes_inicio: u32,
+    cortes_final: u32,
+}
+
+struct PaginacaoPiso {
+    inteiras: u32,
+    cortadas: u32,
+    pecas_para_cortes: u32, // peças novas abertas para tirar os cortes
+    sobras: Vec<(Decimal, Decimal)>,
+    comprimento_juntas: Decimal,
 }
 
 #[derive(PartialEq, Clone, Copy)]
@@ -100,10 +381,116 @@ struct CalculadoraForro {
     direcao_global: DirecaoForro,
 }
 
+// Plano de corte das réguas de forro de todos os ambientes, com as sobras
+// reaproveitadas entre fileiras e ambientes
+struct PlanoForro {
+    pecas: Vec<[u32; 4]>,        // réguas novas abertas por ambiente, por tamanho
+    juntas: Vec<u32>,            // emendas por ambiente
+    comprado: Vec<Decimal>,      // metros de réguas novas por ambiente
+    reaproveitado: Vec<Decimal>, // metros tirados de sobras
+    desperdicio: Vec<Decimal>,   // sobras das réguas do ambiente que não foram usadas
+}
+
+// Sobra de uma régua aberta, lembrando o ambiente que a comprou
+struct SobraForro {
+    comprimento: Decimal,
+    ambiente: usize,
+}
+
 #[derive(PartialEq, Clone, Copy)]
 enum TipoLaje {
     Trelica,
     Isopor,
+    Macica,
+    Nervurada,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum ReferenciaMedida {
+    FaceInterna,
+    EixoParede,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum UsoLaje {
+    Residencial,
+    Comercial,
+    Cobertura,
+}
+
+struct BlocoLaje {
+    nome: &'static str,
+    tipo: TipoLaje,
+    altura: u32,   // cm
+    peso: Decimal, // kg por unidade
+}
+
+struct Cubeta {
+    nome: &'static str,
+    modulo: Decimal,       // distância entre eixos das nervuras (m)
+    altura: Decimal,       // m
+    volume_vazio: Decimal, // concreto economizado por fôrma (m³)
+}
+
+// Geometria de um ambiente calculado, usada no desenho da laje
+struct DesenhoLaje {
+    ambiente: usize,
+    tipo: TipoLaje,
+    lado_instalacao: Decimal,
+    lado_perpendicular: Decimal,
+    comprimento_ajustado: Decimal,
+    largura_ajustada: Decimal,
+    num_vigas: u32,
+    modulo: Option<Decimal>, // distância entre vigas ou entre nervuras
+}
+
+// Elementos de desenho em metros, com origem no canto do ambiente
+enum FormaDesenho {
+    Retangulo { x: f32, y: f32, largura: f32, altura: f32, cor: [u8; 3], preenchido: bool },
+    Linha { x1: f32, y1: f32, x2: f32, y2: f32, cor: [u8; 3], espessura: f32 },
+    Poligono { pontos: Vec<(f32, f32)>, cor: [u8; 3], preenchido: bool },
+    Texto { x: f32, y: f32, texto: String },
+}
+
+struct ResumoTipoLaje {
+    tipo: TipoLaje,
+    area: Decimal,
+    vigas: HashMap<Decimal, u32>,
+    blocos: HashMap<&'static str, u32>,
+    volume_concreto: Decimal,
+}
+
+struct ModeloTrelica {
+    nome: &'static str,
+    altura_laje: u32, // altura total da laje (cm)
+    vao_cobertura: Decimal,
+    vao_residencial: Decimal,
+    vao_comercial: Decimal,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum TipoArmadura {
+    TelaSoldada,
+    Barras,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum ModeloTela {
+    Q61,
+    Q92,
+    Q138,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum PainelTela {
+    Painel2x3,
+    Painel245x6,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum TipoEscora {
+    Metalica,
+    Madeira,
 }
 
 #[derive(PartialEq, Clone, Copy)]
@@ -116,9 +503,37 @@ enum ModoEntradaMateriais {
 struct Ambiente {
     largura: String,
     comprimento: String,
+    produto: Option<usize>, // índice no catálogo; None usa os dados gerais
+    portas: String,         // larguras dos vãos de porta, separadas por ';' (m)
+    paredes: Vec<Parede>,   // usadas no modo de revestimento de parede
+    impermeabilizar: bool,
+    perimetro_box: String, // trecho de parede do box, impermeabilizado até a altura do box (m)
+    cor_rejunte: String,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
+struct Parede {
+    comprimento: String,
+    altura: String,    // altura revestida (m)
+    aberturas: String, // janelas e portas como "largura x altura", separadas por ';'
+}
+
+impl Default for Ambiente {
+    fn default() -> Self {
+        Self {
+            largura: String::new(),
+            comprimento: String::new(),
+            produto: None,
+            portas: String::new(),
+            paredes: vec![Parede::default()],
+            impermeabilizar: true,
+            perimetro_box: String::new(),
+            cor_rejunte: String::new(),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
 struct AmbienteForro {
     largura: String,
     comprimento: String,
@@ -129,26 +544,45 @@ struct AmbienteLaje {
     largura: String,
     comprimento: String,
     direcao: DirecaoLaje,
+    tipo_laje: TipoLaje,
+    bloco: usize, // índice em BLOCOS_LAJE
 }
 
 #[derive(PartialEq, Clone, Copy)]
 enum DirecaoForro {
     MaiorLado,
     MenorLado,
+    Automatico,
 }
 
 #[derive(PartialEq, Clone, Copy)]
 enum DirecaoLaje {
     MaiorLado,
     MenorLado,
+    Automatico,
+}
+
+// Disposição das vigas de um ambiente em uma direção
+struct LayoutLaje {
+    direcao: DirecaoLaje,
+    lado_instalacao: Decimal,
+    lado_perpendicular: Decimal,
+    comprimento_ajustado: Decimal,
+    largura_ajustada: Decimal,
+    num_vigas: u32,
 }
 
 impl Default for CalculadoraConstrucao {
     fn default() -> Self {
         let config: Configuracao = confy::load("calculadora_construcao", None).unwrap_or_default();
 
+        let mut calculadora_laje = CalculadoraLaje::default();
+        if !config.arquivo_desenho_laje.is_empty() {
+            calculadora_laje.arquivo_svg = config.arquivo_desenho_laje.clone();
+        }
+
         Self {
-            calculadora_laje: CalculadoraLaje::default(),
+            calculadora_laje,
             calculadora_piso: CalculadoraPiso::with_config(&config),
             calculadora_materiais: CalculadoraMateriais::default(),
             calculadora_basica: CalculadoraBasica::new(),
@@ -162,9 +596,469 @@ impl Default for CalculadoraConstrucao {
 impl Default for CalculadoraLaje {
     fn default() -> Self {
         Self {
-            tipo_laje: TipoLaje::Trelica,
+            uso_laje: UsoLaje::Residencial,
             ambientes: vec![AmbienteLaje::default()],
             resultado: String::new(),
+            desenhos: Vec::new(),
+            mensagem_exportacao: String::new(),
+            arquivo_svg: "desenho_laje.svg".to_string(),
+            confirmar_sobrescrita: None,
+            referencia_medida: ReferenciaMedida::FaceInterna,
+            espessura_parede: "0.15".to_string(),
+            apoio_viga: "0.05".to_string(),
+            espessura_macica: "0.10".to_string(),
+            reaproveitamento_forma: "3".to_string(),
+            taxa_aco_macica: "80".to_string(),
+            espessura_capa: "0.05".to_string(),
+            cubeta_nervurada: 0,
+            calcular_cargas: false,
+            carga_revestimento: "1.0".to_string(),
+            carga_acidental: UsoLaje::Residencial.carga_acidental().to_string(),
+            calcular_armadura: false,
+            tipo_armadura: TipoArmadura::TelaSoldada,
+            modelo_tela: ModeloTela::Q92,
+            painel_tela: PainelTela::Painel2x3,
+            transpasse_tela: "0.20".to_string(),
+            diametro_barra: "5.0".to_string(),
+            espacamento_barra: "0.20".to_string(),
+            calcular_escoramento: false,
+            tipo_escora: TipoEscora::Metalica,
+            espacamento_linhas_escora: "1.00".to_string(),
+            espacamento_escoras: "1.00".to_string(),
+            valor_diaria_escora: String::new(),
+            dias_locacao: "21".to_string(),
+            calcular_plano_corte: false,
+            comprimento_estoque_viga: "12.00".to_string(),
+            sobras_vigas: String::new(),
+        }
+    }
+}
+
+impl TipoLaje {
+    fn nome(&self) -> &'static str {
+        match self {
+            TipoLaje::Trelica => "Treliça",
+            TipoLaje::Isopor => "Isopor",
+            TipoLaje::Macica => "Maciça",
+            TipoLaje::Nervurada => "Nervurada",
+        }
+    }
+
+    // A laje maciça não tem elementos de enchimento
+    fn nome_elementos(&self) -> Option<&'static str> {
+        match self {
+            TipoLaje::Trelica => Some("Lajotas"),
+            TipoLaje::Isopor => Some("Placas de isopor"),
+            TipoLaje::Macica => None,
+            TipoLaje::Nervurada => Some("Cubetas"),
+        }
+    }
+
+    // Distância entre vigas; lajes moldadas no local não têm vigas
+    fn largura_step(&self) -> Option<Decimal> {
+        match self {
+            TipoLaje::Trelica => Some(LARGURA_TRELICA),
+            TipoLaje::Isopor => Some(LARGURA_ISOPOR),
+            TipoLaje::Macica | TipoLaje::Nervurada => None,
+        }
+    }
+
+    fn possui_blocos(&self) -> bool {
+        BLOCOS_LAJE.iter().any(|bloco| bloco.tipo == *self)
+    }
+
+    // Bloco H8 do tipo, ou o primeiro bloco cadastrado para ele
+    fn bloco_padrao(&self) -> usize {
+        BLOCOS_LAJE
+            .iter()
+            .position(|bloco| bloco.tipo == *self && bloco.altura == 8)
+            .or_else(|| BLOCOS_LAJE.iter().position(|bloco| bloco.tipo == *self))
+            .unwrap_or(0)
+    }
+}
+
+impl DirecaoLaje {
+    fn nome(&self) -> &'static str {
+        match self {
+            DirecaoLaje::MaiorLado => "Maior lado",
+            DirecaoLaje::MenorLado => "Menor lado",
+            DirecaoLaje::Automatico => "Automático",
+        }
+    }
+}
+
+impl DirecaoForro {
+    fn nome(&self) -> &'static str {
+        match self {
+            DirecaoForro::MaiorLado => "Maior lado",
+            DirecaoForro::MenorLado => "Menor lado",
+            DirecaoForro::Automatico => "Automático",
+        }
+    }
+}
+
+impl LayoutLaje {
+    fn metros_vigas(&self) -> Decimal {
+        Decimal::from(self.num_vigas) * self.comprimento_ajustado
+    }
+}
+
+impl DesenhoLaje {
+    const COR_CONTORNO: [u8; 3] = [150, 150, 150];
+    const COR_VIGA: [u8; 3] = [230, 140, 40];
+    const COR_BLOCO: [u8; 3] = [170, 90, 60];
+    const COR_EPS: [u8; 3] = [210, 210, 230];
+    const COR_CONCRETO: [u8; 3] = [120, 120, 120];
+    const COR_JUNTA: [u8; 3] = [60, 60, 60];
+
+    fn formas(&self) -> Vec<FormaDesenho> {
+        let metros = |valor: Decimal| valor.to_f32().unwrap_or(0.0);
+        let comprimento = metros(self.lado_instalacao);
+        let largura = metros(self.lado_perpendicular);
+        let apoio = (metros(self.comprimento_ajustado) - comprimento) / 2.0;
+        let mut formas = Vec::new();
+
+        match (self.tipo, self.modulo) {
+            (TipoLaje::Trelica | TipoLaje::Isopor, Some(modulo)) => {
+                let modulo = metros(modulo);
+                let largura_ajustada = metros(self.largura_ajustada);
+                let (cor_bloco, passo_bloco) = if self.tipo == TipoLaje::Trelica {
+                    (Self::COR_BLOCO, 0.2)
+                } else {
+                    (Self::COR_EPS, 0.5)
+                };
+
+                // Blocos de enchimento ocupam toda a faixa ajustada
+                formas.push(FormaDesenho::Retangulo {
+                    x: 0.0,
+                    y: 0.0,
+                    largura: comprimento,
+                    altura: largura_ajustada,
+                    cor: cor_bloco,
+                    preenchido: true,
+                });
+                let mut x = passo_bloco;
+                while x < comprimento {
+                    formas.push(FormaDesenho::Linha {
+                        x1: x,
+                        y1: 0.0,
+                        x2: x,
+                        y2: largura_ajustada,
+                        cor: Self::COR_JUNTA,
+                        espessura: 0.5,
+                    });
+                    x += passo_bloco;
+                }
+
+                // Vigas avançam sobre as paredes pelo comprimento do apoio
+                for indice in 0..self.num_vigas {
+                    let y = (indice as f32 + 0.5) * modulo;
+                    formas.push(FormaDesenho::Linha {
+                        x1: -apoio,
+                        y1: y,
+                        x2: comprimento + apoio,
+                        y2: y,
+                        cor: Self::COR_VIGA,
+                        espessura: 3.0,
+                    });
+                }
+
+                formas.push(FormaDesenho::Texto {
+                    x: comprimento / 2.0,
+                    y: -0.3,
+                    texto: format!(
+                        "Vão {:.2} m | Vigas de {:.2} m",
+                        self.lado_instalacao, self.comprimento_ajustado
+                    ),
+                });
+                formas.push(FormaDesenho::Texto {
+                    x: comprimento / 2.0,
+                    y: largura_ajustada.max(largura) + 0.3,
+                    texto: format!(
+                        "Largura {:.2} m (ajustada {:.2} m) | {} vigas a cada {:.2} m",
+                        self.lado_perpendicular,
+                        self.largura_ajustada,
+                        self.num_vigas,
+                        modulo
+                    ),
+                });
+            }
+            (TipoLaje::Nervurada, Some(modulo)) => {
+                let modulo = metros(modulo);
+                let fileiras_x = (comprimento / modulo).floor();
+                let fileiras_y = (largura / modulo).floor();
+                // Fôrmas centralizadas; a sobra junto às paredes fica maciça
+                let inicio_x = (comprimento - fileiras_x * modulo) / 2.0;
+                let inicio_y = (largura - fileiras_y * modulo) / 2.0;
+                let nervura = 0.1;
+
+                formas.push(FormaDesenho::Retangulo {
+                    x: 0.0,
+                    y: 0.0,
+                    largura: comprimento,
+                    altura: largura,
+                    cor: Self::COR_CONCRETO,
+                    preenchido: true,
+                });
+                for i in 0..fileiras_x as u32 {
+                    for j in 0..fileiras_y as u32 {
+                        formas.push(FormaDesenho::Retangulo {
+                            x: inicio_x + i as f32 * modulo + nervura / 2.0,
+                            y: inicio_y + j as f32 * modulo + nervura / 2.0,
+                            largura: modulo - nervura,
+                            altura: modulo - nervura,
+                            cor: Self::COR_EPS,
+                            preenchido: true,
+                        });
+                    }
+                }
+
+                formas.push(FormaDesenho::Texto {
+                    x: comprimento / 2.0,
+                    y: largura + 0.3,
+                    texto: format!(
+                        "{:.2} x {:.2} m | {} cubetas",
+                        self.lado_instalacao,
+                        self.lado_perpendicular,
+                        fileiras_x as u32 * fileiras_y as u32
+                    ),
+                });
+            }
+            _ => {
+                formas.push(FormaDesenho::Retangulo {
+                    x: 0.0,
+                    y: 0.0,
+                    largura: comprimento,
+                    altura: largura,
+                    cor: Self::COR_CONCRETO,
+                    preenchido: true,
+                });
+                formas.push(FormaDesenho::Texto {
+                    x: comprimento / 2.0,
+                    y: largura + 0.3,
+                    texto: format!(
+                        "Laje maciça {:.2} x {:.2} m",
+                        self.lado_instalacao, self.lado_perpendicular
+                    ),
+                });
+            }
+        }
+
+        // Contorno interno do ambiente (face das paredes)
+        formas.push(FormaDesenho::Retangulo {
+            x: 0.0,
+            y: 0.0,
+            largura: comprimento,
+            altura: largura,
+            cor: Self::COR_CONTORNO,
+            preenchido: false,
+        });
+
+        formas
+    }
+}
+
+impl ProdutoPiso {
+    fn revestimento(&self) -> Result<RevestimentoPiso, String> {
+        if self.nome.trim().is_empty() {
+            return Err("Nome do produto inválido".to_string());
+        }
+
+        let positivo = |valor: &str, erro: &str| match parse_decimal(valor) {
+            Ok(v) if v > Decimal::ZERO => Ok(v),
+            _ => Err(format!("Produto {}: {}", self.nome, erro)),
+        };
+        let opcional = |valor: &str, erro: &str| {
+            if valor.trim().is_empty() {
+                Ok(None)
+            } else {
+                positivo(valor, erro).map(Some)
+            }
+        };
+
+        Ok(RevestimentoPiso {
+            nome: self.nome.clone(),
+            largura: positivo(&self.largura, "largura inválida")?,
+            comprimento: positivo(&self.comprimento, "comprimento inválido")?,
+            espessura: positivo(&self.espessura, "espessura inválida")?,
+            area_caixa: positivo(&self.area_caixa, "área da caixa inválida")?,
+            pecas_caixa: match self.pecas_caixa.trim().parse::<u32>() {
+                Ok(v) if v > 0 => v,
+                _ => return Err(format!("Produto {}: peças por caixa inválido", self.nome)),
+            },
+            peso_caixa: opcional(&self.peso_caixa, "peso da caixa inválido")?,
+            preco_caixa: opcional(&self.preco_caixa, "preço da caixa inválido")?,
+            porcelanato: self.porcelanato,
+        })
+    }
+}
+
+impl Impermeabilizante {
+    fn nome(&self) -> &'static str {
+        match self {
+            Impermeabilizante::MantaLiquida => "Manta Líquida",
+            Impermeabilizante::Cimenticia => "Argamassa Polimérica (cimentícia)",
+        }
+    }
+
+    fn unidade(&self) -> &'static str {
+        match self {
+            Impermeabilizante::MantaLiquida => "L",
+            Impermeabilizante::Cimenticia => "kg",
+        }
+    }
+
+    // Consumo de referência por m² e por demão
+    fn consumo_padrao(&self) -> &'static str {
+        match self {
+            Impermeabilizante::MantaLiquida => "0.5",
+            Impermeabilizante::Cimenticia => "1.0",
+        }
+    }
+
+    fn embalagens(&self) -> [Decimal; 2] {
+        match self {
+            Impermeabilizante::MantaLiquida => [dec!(18), dec!(3.6)],
+            Impermeabilizante::Cimenticia => [dec!(18), dec!(4)],
+        }
+    }
+}
+
+impl InicioPaginacao {
+    fn nome(&self) -> &'static str {
+        match self {
+            InicioPaginacao::Canto => "Canto",
+            InicioPaginacao::Centro => "Centro",
+        }
+    }
+}
+
+impl LocalAssentamento {
+    fn nome(&self) -> &'static str {
+        match self {
+            LocalAssentamento::Interno => "Interno",
+            LocalAssentamento::Externo => "Externo",
+            LocalAssentamento::Piscina => "Piscina",
+            LocalAssentamento::Fachada => "Fachada",
+        }
+    }
+
+    // Tipo de argamassa colante (NBR 14081) e se a peça pede colagem dupla
+    fn recomendar_argamassa(&self, revestimento: &RevestimentoPiso) -> (&'static str, bool) {
+        let grande = revestimento.porcelanato
+            && revestimento.largura.max(revestimento.comprimento) > LADO_MAXIMO_COLAGEM_SIMPLES;
+        let dupla = grande
+            || (*self != LocalAssentamento::Interno
+                && revestimento.largura * revestimento.comprimento
+                    > AREA_MAXIMA_COLAGEM_SIMPLES_EXTERNA);
+
+        let tipo = match self {
+            LocalAssentamento::Piscina | LocalAssentamento::Fachada => "AC-III",
+            _ if grande => "AC-III",
+            LocalAssentamento::Externo => "AC-II",
+            LocalAssentamento::Interno if revestimento.porcelanato => "AC-II",
+            LocalAssentamento::Interno => "AC-I",
+        };
+        (tipo, dupla)
+    }
+}
+
+impl PadraoAssentamento {
+    // Cruzetas por peça: a prumo cada cruzamento é dividido por 4 peças; com
+    // juntas desencontradas viram encontros em T, cada um com o seu espaçador
+    fn espacadores_por_peca(&self) -> Decimal {
+        match self {
+            PadraoAssentamento::Reto | PadraoAssentamento::Diagonal => dec!(1),
+            PadraoAssentamento::AmarracaoMeia
+            | PadraoAssentamento::AmarracaoTerco
+            | PadraoAssentamento::EspinhaDePeixe => dec!(2),
+        }
+    }
+
+    // A prumo e em amarração as juntas formam linhas contínuas e o comprimento por m²
+    // sai direto da fórmula; nos demais padrões ele vem da paginação
+    fn juntas_pela_formula(&self) -> bool {
+        match self {
+            PadraoAssentamento::Reto
+            | PadraoAssentamento::AmarracaoMeia
+            | PadraoAssentamento::AmarracaoTerco => true,
+            PadraoAssentamento::Diagonal | PadraoAssentamento::EspinhaDePeixe => false,
+        }
+    }
+
+    // Perda por cortes sugerida (%) para o cálculo por área
+    fn perda_sugerida(&self) -> Decimal {
+        match self {
+            PadraoAssentamento::Reto => dec!(5),
+            PadraoAssentamento::AmarracaoMeia | PadraoAssentamento::AmarracaoTerco => dec!(8),
+            PadraoAssentamento::Diagonal => dec!(12),
+            PadraoAssentamento::EspinhaDePeixe => dec!(15),
+        }
+    }
+
+    fn nome(&self) -> &'static str {
+        match self {
+            PadraoAssentamento::Reto => "Reto (junta a prumo)",
+            PadraoAssentamento::AmarracaoMeia => "Amarração 1/2",
+            PadraoAssentamento::AmarracaoTerco => "Amarração 1/3",
+            PadraoAssentamento::Diagonal => "Diagonal 45°",
+            PadraoAssentamento::EspinhaDePeixe => "Espinha de Peixe",
+        }
+    }
+}
+
+impl UsoLaje {
+    // Carga acidental de referência (kN/m²)
+    fn carga_acidental(&self) -> &'static str {
+        match self {
+            UsoLaje::Residencial => "1.5",
+            UsoLaje::Comercial => "3.0",
+            UsoLaje::Cobertura => "0.5",
+        }
+    }
+}
+
+impl ModeloTrelica {
+    fn vao_maximo(&self, uso: UsoLaje) -> Decimal {
+        match uso {
+            UsoLaje::Residencial => self.vao_residencial,
+            UsoLaje::Comercial => self.vao_comercial,
+            UsoLaje::Cobertura => self.vao_cobertura,
+        }
+    }
+}
+
+impl BarraCorte {
+    fn sobra(&self) -> Decimal {
+        self.comprimento - self.cortes.iter().sum::<Decimal>()
+    }
+}
+
+impl ModeloTela {
+    fn nome(&self) -> &'static str {
+        match self {
+            ModeloTela::Q61 => "Q-61",
+            ModeloTela::Q92 => "Q-92",
+            ModeloTela::Q138 => "Q-138",
+        }
+    }
+
+    // Peso nominal da tela em kg/m²
+    fn peso_m2(&self) -> Decimal {
+        match self {
+            ModeloTela::Q61 => dec!(0.97),
+            ModeloTela::Q92 => dec!(1.48),
+            ModeloTela::Q138 => dec!(2.20),
+        }
+    }
+}
+
+impl PainelTela {
+    // (largura, comprimento) do painel em metros
+    fn dimensoes(&self) -> (Decimal, Decimal) {
+        match self {
+            PainelTela::Painel2x3 => (dec!(2.0), dec!(3.0)),
+            PainelTela::Painel245x6 => (dec!(2.45), dec!(6.0)),
         }
     }
 }
@@ -175,6 +1069,8 @@ impl Default for AmbienteLaje {
             largura: String::new(),
             comprimento: String::new(),
             direcao: DirecaoLaje::MenorLado,
+            tipo_laje: TipoLaje::Trelica,
+            bloco: TipoLaje::Trelica.bloco_padrao(),
         }
     }
 }
@@ -186,6 +1082,11 @@ impl CalculadoraPiso {
             s => s.to_string(),
         };
 
+        let fator_colagem_dupla = match config.fator_argamassa_dupla.as_str() {
+            "" => "7.0".to_string(),
+            s => s.to_string(),
+        };
+
         let rejunte_coeficiente = if !config.coeficiente_rejunte.is_empty() {
             config.coeficiente_rejunte.clone()
         } else {
@@ -193,19 +1094,57 @@ impl CalculadoraPiso {
         };
 
         Self {
+            modo: ModoRevestimento::Piso,
             area_caixa: String::new(),
             ambientes: vec![Ambiente::default()],
             calcular_piso: true,
             calcular_argamassa: false,
             calcular_rejunte: false,
+            calcular_espacadores: false,
             resultado: String::new(),
             tile_width: String::new(),
             tile_length: String::new(),
             tile_thickness: "3".to_string(),
             joint_spacing: "2".to_string(),
             rejunte_coeficiente,
+            precos_rejunte: Default::default(),
             application_method: ApplicationMethod::SingleSided,
             argamassa_fator,
+            fator_colagem_dupla,
+            local_assentamento: LocalAssentamento::Interno,
+            porcelanato: false,
+            simular_paginacao: false,
+            pecas_caixa: String::new(),
+            padrao_assentamento: PadraoAssentamento::Reto,
+            inicio_paginacao: InicioPaginacao::Canto,
+            desenhos_paginacao: Vec::new(),
+            percentual_perda: PadraoAssentamento::Reto.perda_sugerida().to_string(),
+            caixas_reserva: "0".to_string(),
+            caixas_palete: String::new(),
+            calcular_rodape: false,
+            tipo_rodape: TipoRodape::Ceramico,
+            altura_rodape: "7".to_string(),
+            comprimento_regua_rodape: "2.40".to_string(),
+            fixacao_rodape: FixacaoRodape::Pregos,
+            comprimento_regua: String::new(),
+            largura_regua: String::new(),
+            area_caixa_laminado: String::new(),
+            deslocamento_regua: "30".to_string(),
+            area_rolo_manta: "20".to_string(),
+            comprimento_perfil_transicao: "0.90".to_string(),
+            calcular_contrapiso: false,
+            espessura_contrapiso: "4".to_string(),
+            traco_cimento: "1".to_string(),
+            traco_areia: "4".to_string(),
+            calcular_impermeabilizacao: false,
+            impermeabilizante: Impermeabilizante::MantaLiquida,
+            consumo_impermeabilizante: Impermeabilizante::MantaLiquida.consumo_padrao().to_string(),
+            demaos_impermeabilizante: "3".to_string(),
+            altura_subida: "0.30".to_string(),
+            altura_box: "1.80".to_string(),
+            produtos: config.produtos_piso.clone(),
+            novo_produto: ProdutoPiso::default(),
+            mensagem_catalogo: String::new(),
         }
     }
 }
@@ -228,15 +1167,6 @@ impl Default for CalculadoraMateriais {
     }
 }
 
-impl Default for Ambiente {
-    fn default() -> Self {
-        Self {
-            largura: String::new(),
-            comprimento: String::new(),
-        }
-    }
-}
-
 impl Default for CalculadoraForro {
     fn default() -> Self {
         Self {
@@ -247,15 +1177,6 @@ impl Default for CalculadoraForro {
     }
 }
 
-impl Default for AmbienteForro {
-    fn default() -> Self {
-        Self {
-            largura: String::new(),
-            comprimento: String::new(),
-        }
-    }
-}
-
 impl eframe::App for CalculadoraConstrucao {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Define o tema escuro
@@ -270,8 +1191,12 @@ impl eframe::App for CalculadoraConstrucao {
                 );
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     if ui.button("Resetar Valores Padrão").clicked() {
-                        confy::store("calculadora_construcao", None, &Configuracao::default())
-                            .unwrap();
+                        // O catálogo de revestimentos não é valor padrão, então é mantido
+                        let config = Configuracao {
+                            produtos_piso: std::mem::take(&mut self.config.produtos_piso),
+                            ..Default::default()
+                        };
+                        confy::store("calculadora_construcao", None, config).unwrap();
                         *self = CalculadoraConstrucao::default();
                     }
                 });
@@ -348,15 +1273,337 @@ impl CalculadoraConstrucao {
             .spacing([10.0, 10.0])
             .min_col_width(100.0)
             .show(ui, |ui| {
-                ui.label("Tipo de Laje:");
+                ui.label("Uso da Laje:");
+                ui.horizontal(|ui| {
+                    let laje = &mut self.calculadora_laje;
+                    for (uso, nome) in [
+                        (UsoLaje::Residencial, "Residencial"),
+                        (UsoLaje::Comercial, "Comercial"),
+                        (UsoLaje::Cobertura, "Cobertura/Forro"),
+                    ] {
+                        if ui.radio_value(&mut laje.uso_laje, uso, nome).clicked() {
+                            laje.carga_acidental = uso.carga_acidental().to_string();
+                        }
+                    }
+                })
+                    .response
+                    .on_hover_text("Define a carga de uso para a escolha do modelo de treliça.");
+                ui.end_row();
+
+                ui.label("Medidas Informadas:");
                 ui.horizontal(|ui| {
-                    ui.radio_value(&mut self.calculadora_laje.tipo_laje, TipoLaje::Trelica, "Treliça");
-                    ui.radio_value(&mut self.calculadora_laje.tipo_laje, TipoLaje::Isopor, "Isopor");
+                    ui.radio_value(
+                        &mut self.calculadora_laje.referencia_medida,
+                        ReferenciaMedida::FaceInterna,
+                        "Face interna das paredes",
+                    );
+                    ui.radio_value(
+                        &mut self.calculadora_laje.referencia_medida,
+                        ReferenciaMedida::EixoParede,
+                        "Eixo das paredes",
+                    );
                 });
                 ui.end_row();
-            });
 
-        ui.add_space(10.0);
+                ui.label("Espessura da Parede (m):");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.calculadora_laje.espessura_parede)
+                        .desired_width(100.0)
+                        .hint_text("Ex: 0.15"),
+                );
+                ui.end_row();
+
+                if self.calculadora_laje.possui_vigas() {
+                    ui.label("Apoio da Viga (m):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.calculadora_laje.apoio_viga)
+                            .desired_width(100.0)
+                            .hint_text("Ex: 0.05"),
+                    )
+                        .on_hover_text("Quanto a viga entra na parede em cada extremidade. Padrão 0.05 m; use 0 para o vão livre.");
+                    ui.end_row();
+                }
+
+                ui.label("Capa de Concreto (m):");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.calculadora_laje.espessura_capa)
+                        .desired_width(100.0)
+                        .hint_text("Ex: 0.05"),
+                )
+                    .on_hover_text("Concreto acima das vigas e blocos (não se aplica à laje maciça).");
+                ui.end_row();
+            });
+
+        ui.add_space(10.0);
+
+        if self
+            .calculadora_laje
+            .ambientes
+            .iter()
+            .any(|ambiente| ambiente.tipo_laje == TipoLaje::Macica)
+        {
+            egui::Grid::new("macica_laje_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .min_col_width(100.0)
+                .show(ui, |ui| {
+                    let laje = &mut self.calculadora_laje;
+
+                    ui.label("Espessura da Maciça (m):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut laje.espessura_macica)
+                            .desired_width(100.0)
+                            .hint_text("Ex: 0.10"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Usos de cada Chapa:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut laje.reaproveitamento_forma)
+                            .desired_width(100.0)
+                            .hint_text("Ex: 3"),
+                    )
+                        .on_hover_text("Quantas concretagens cada chapa de compensado aguenta.");
+                    ui.end_row();
+
+                    ui.label("Taxa de Aço (kg/m³):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut laje.taxa_aco_macica)
+                            .desired_width(100.0)
+                            .hint_text("Ex: 80"),
+                    );
+                    ui.end_row();
+                });
+
+            ui.add_space(10.0);
+        }
+
+        if self
+            .calculadora_laje
+            .ambientes
+            .iter()
+            .any(|ambiente| ambiente.tipo_laje == TipoLaje::Nervurada)
+        {
+            egui::Grid::new("nervurada_laje_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .min_col_width(100.0)
+                .show(ui, |ui| {
+                    let laje = &mut self.calculadora_laje;
+
+                    ui.label("Fôrma da Nervurada:");
+                    egui::ComboBox::from_id_source("cubeta_nervurada")
+                        .selected_text(CUBETAS_NERVURADA[laje.cubeta_nervurada].nome)
+                        .show_ui(ui, |ui| {
+                            for (indice, cubeta) in CUBETAS_NERVURADA.iter().enumerate() {
+                                ui.selectable_value(&mut laje.cubeta_nervurada, indice, cubeta.nome);
+                            }
+                        });
+                    ui.end_row();
+                });
+
+            ui.add_space(10.0);
+        }
+
+        ui.checkbox(
+            &mut self.calculadora_laje.calcular_armadura,
+            "Calcular Armadura de Distribuição",
+        );
+
+        if self.calculadora_laje.calcular_armadura {
+            ui.add_space(5.0);
+            egui::Grid::new("armadura_laje_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .min_col_width(100.0)
+                .show(ui, |ui| {
+                    let laje = &mut self.calculadora_laje;
+
+                    ui.label("Tipo de Armadura:");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut laje.tipo_armadura, TipoArmadura::TelaSoldada, "Tela Soldada");
+                        ui.radio_value(&mut laje.tipo_armadura, TipoArmadura::Barras, "Barras");
+                    });
+                    ui.end_row();
+
+                    match laje.tipo_armadura {
+                        TipoArmadura::TelaSoldada => {
+                            ui.label("Modelo da Tela:");
+                            ui.horizontal(|ui| {
+                                ui.radio_value(&mut laje.modelo_tela, ModeloTela::Q61, "Q-61");
+                                ui.radio_value(&mut laje.modelo_tela, ModeloTela::Q92, "Q-92");
+                                ui.radio_value(&mut laje.modelo_tela, ModeloTela::Q138, "Q-138");
+                            });
+                            ui.end_row();
+
+                            ui.label("Painel:");
+                            ui.horizontal(|ui| {
+                                ui.radio_value(&mut laje.painel_tela, PainelTela::Painel2x3, "2,00 x 3,00 m");
+                                ui.radio_value(&mut laje.painel_tela, PainelTela::Painel245x6, "2,45 x 6,00 m");
+                            });
+                            ui.end_row();
+
+                            ui.label("Transpasse (m):");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut laje.transpasse_tela)
+                                    .desired_width(100.0)
+                                    .hint_text("Ex: 0.20"),
+                            )
+                                .on_hover_text("Sobreposição entre painéis vizinhos, nas duas direções.");
+                            ui.end_row();
+                        }
+                        TipoArmadura::Barras => {
+                            ui.label("Diâmetro da Barra (mm):");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut laje.diametro_barra)
+                                    .desired_width(100.0)
+                                    .hint_text("Ex: 5.0"),
+                            );
+                            ui.end_row();
+
+                            ui.label("Espaçamento (m):");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut laje.espacamento_barra)
+                                    .desired_width(100.0)
+                                    .hint_text("Ex: 0.20"),
+                            )
+                                .on_hover_text("Barras dispostas perpendicularmente às vigas.");
+                            ui.end_row();
+                        }
+                    }
+                });
+        }
+
+        ui.add_space(5.0);
+
+        ui.checkbox(
+            &mut self.calculadora_laje.calcular_cargas,
+            "Calcular Peso Próprio e Cargas",
+        );
+
+        if self.calculadora_laje.calcular_cargas {
+            ui.add_space(5.0);
+            egui::Grid::new("cargas_laje_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .min_col_width(100.0)
+                .show(ui, |ui| {
+                    let laje = &mut self.calculadora_laje;
+
+                    ui.label("Revestimento (kN/m²):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut laje.carga_revestimento)
+                            .desired_width(100.0)
+                            .hint_text("Ex: 1.0"),
+                    )
+                        .on_hover_text("Contrapiso, piso e forro somados ao peso próprio.");
+                    ui.end_row();
+
+                    ui.label("Carga Acidental (kN/m²):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut laje.carga_acidental)
+                            .desired_width(100.0)
+                            .hint_text("Ex: 1.5"),
+                    )
+                        .on_hover_text("Preenchida conforme o uso da laje; ajuste se necessário.");
+                    ui.end_row();
+                });
+        }
+
+        ui.add_space(5.0);
+
+        ui.checkbox(
+            &mut self.calculadora_laje.calcular_escoramento,
+            "Calcular Escoramento",
+        );
+
+        if self.calculadora_laje.calcular_escoramento {
+            ui.add_space(5.0);
+            egui::Grid::new("escoramento_laje_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .min_col_width(100.0)
+                .show(ui, |ui| {
+                    let laje = &mut self.calculadora_laje;
+
+                    ui.label("Tipo de Escora:");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut laje.tipo_escora, TipoEscora::Metalica, "Metálica");
+                        ui.radio_value(&mut laje.tipo_escora, TipoEscora::Madeira, "Madeira");
+                    });
+                    ui.end_row();
+
+                    ui.label("Distância entre Linhas (m):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut laje.espacamento_linhas_escora)
+                            .desired_width(100.0)
+                            .hint_text("Ex: 1.00"),
+                    )
+                        .on_hover_text("Linhas de guias perpendiculares às vigas.");
+                    ui.end_row();
+
+                    ui.label("Distância entre Escoras (m):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut laje.espacamento_escoras)
+                            .desired_width(100.0)
+                            .hint_text("Ex: 1.00"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Diária por Escora (R$):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut laje.valor_diaria_escora)
+                            .desired_width(100.0)
+                            .hint_text("Opcional"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Dias de Locação:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut laje.dias_locacao)
+                            .desired_width(100.0)
+                            .hint_text("Ex: 21"),
+                    );
+                    ui.end_row();
+                });
+        }
+
+        ui.add_space(5.0);
+
+        ui.checkbox(
+            &mut self.calculadora_laje.calcular_plano_corte,
+            "Gerar Plano de Corte das Vigas",
+        );
+
+        if self.calculadora_laje.calcular_plano_corte {
+            ui.add_space(5.0);
+            egui::Grid::new("plano_corte_laje_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .min_col_width(100.0)
+                .show(ui, |ui| {
+                    let laje = &mut self.calculadora_laje;
+
+                    ui.label("Barra de Estoque (m):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut laje.comprimento_estoque_viga)
+                            .desired_width(100.0)
+                            .hint_text("Ex: 12.00"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Sobras Disponíveis (m):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut laje.sobras_vigas)
+                            .desired_width(200.0)
+                            .hint_text("Ex: 3.40; 2.10"),
+                    )
+                        .on_hover_text("Sobras de obras anteriores, separadas por ponto e vírgula. São usadas antes das barras novas.");
+                    ui.end_row();
+                });
+        }
+
+        ui.add_space(10.0);
 
         let mut ambiente_para_remover = None;
         let ambientes_len = self.calculadora_laje.ambientes.len();
@@ -392,20 +1639,60 @@ impl CalculadoraConstrucao {
                                 );
                                 ui.end_row();
 
-                                ui.label("Direção de Instalação:");
+                                if ambiente.tipo_laje.largura_step().is_some() {
+                                    ui.label("Direção de Instalação:");
+                                    ui.horizontal(|ui| {
+                                        ui.radio_value(
+                                            &mut ambiente.direcao,
+                                            DirecaoLaje::MenorLado,
+                                            "Menor Lado",
+                                        );
+                                        ui.radio_value(
+                                            &mut ambiente.direcao,
+                                            DirecaoLaje::MaiorLado,
+                                            "Maior Lado",
+                                        );
+                                        ui.radio_value(
+                                            &mut ambiente.direcao,
+                                            DirecaoLaje::Automatico,
+                                            "Automático",
+                                        )
+                                            .on_hover_text("Compara as duas direções e escolhe a de menor consumo de vigas.");
+                                    });
+                                    ui.end_row();
+                                }
+
+                                ui.label("Tipo de Laje:");
                                 ui.horizontal(|ui| {
-                                    ui.radio_value(
-                                        &mut ambiente.direcao,
-                                        DirecaoLaje::MenorLado,
-                                        "Menor Lado",
-                                    );
-                                    ui.radio_value(
-                                        &mut ambiente.direcao,
-                                        DirecaoLaje::MaiorLado,
-                                        "Maior Lado",
-                                    );
+                                    for tipo in [
+                                        TipoLaje::Trelica,
+                                        TipoLaje::Isopor,
+                                        TipoLaje::Macica,
+                                        TipoLaje::Nervurada,
+                                    ] {
+                                        if ui
+                                            .radio_value(&mut ambiente.tipo_laje, tipo, tipo.nome())
+                                            .clicked()
+                                        {
+                                            ambiente.bloco = tipo.bloco_padrao();
+                                        }
+                                    }
                                 });
                                 ui.end_row();
+
+                                if ambiente.tipo_laje.possui_blocos() {
+                                    ui.label("Bloco de Enchimento:");
+                                    egui::ComboBox::from_id_source(format!("bloco_laje_{}", index))
+                                        .selected_text(BLOCOS_LAJE[ambiente.bloco].nome)
+                                        .show_ui(ui, |ui| {
+                                            for (indice, bloco) in BLOCOS_LAJE.iter().enumerate() {
+                                                if bloco.tipo == ambiente.tipo_laje {
+                                                    ui.selectable_value(&mut ambiente.bloco, indice, bloco.nome);
+                                                }
+                                            }
+                                        });
+                                    ui.end_row();
+                                }
                             });
 
                         ui.add_space(5.0);
@@ -434,11 +1721,35 @@ impl CalculadoraConstrucao {
             if ui.button("Calcular Laje").clicked() {
                 self.calculadora_laje.calcular();
             }
+            if ui.button("Exportar Desenho (SVG)").clicked() {
+                self.calculadora_laje.exportar_svg();
+                if self.config.arquivo_desenho_laje != self.calculadora_laje.arquivo_svg {
+                    self.config.arquivo_desenho_laje = self.calculadora_laje.arquivo_svg.clone();
+                    confy::store("calculadora_construcao", None, &self.config).unwrap();
+                }
+            }
             if ui.button("Resetar Valores").clicked() {
-                self.calculadora_laje = CalculadoraLaje::default();
+                self.calculadora_laje = CalculadoraLaje {
+                    arquivo_svg: std::mem::take(&mut self.calculadora_laje.arquivo_svg),
+                    ..Default::default()
+                };
             }
         });
 
+        ui.horizontal(|ui| {
+            ui.label("Arquivo do Desenho:");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.calculadora_laje.arquivo_svg)
+                    .desired_width(250.0)
+                    .hint_text("Ex: desenho_laje.svg"),
+            )
+                .on_hover_text("Caminho do arquivo SVG exportado. Um arquivo existente só é substituído após confirmação.");
+        });
+
+        if !self.calculadora_laje.mensagem_exportacao.is_empty() {
+            ui.label(&self.calculadora_laje.mensagem_exportacao);
+        }
+
         ui.add_space(15.0);
         ui.separator();
         ui.add_space(15.0);
@@ -451,6 +1762,12 @@ impl CalculadoraConstrucao {
                     RichText::new(&self.calculadora_laje.resultado)
                         .color(Color32::WHITE),
                 );
+
+                for desenho in &self.calculadora_laje.desenhos {
+                    ui.collapsing(format!("Desenho do Ambiente {}", desenho.ambiente), |ui| {
+                        desenhar_formas(ui, &desenho.formas());
+                    });
+                }
             });
     }
 
@@ -462,22 +1779,61 @@ impl CalculadoraConstrucao {
 
         ui.add_space(10.0);
 
+        ui.horizontal(|ui| {
+            ui.radio_value(
+                &mut self.calculadora_piso.modo,
+                ModoRevestimento::Piso,
+                "Piso",
+            );
+            ui.radio_value(
+                &mut self.calculadora_piso.modo,
+                ModoRevestimento::Parede,
+                "Revestimento de Parede",
+            );
+            ui.radio_value(
+                &mut self.calculadora_piso.modo,
+                ModoRevestimento::Laminado,
+                "Piso Laminado/Vinílico",
+            );
+        });
+
+        ui.add_space(5.0);
+
         // Opções de cálculo
         ui.horizontal(|ui| {
-            if ui.checkbox(&mut self.calculadora_piso.calcular_piso, "Calcular Piso").clicked() {
-                self.calculadora_piso.validar_selecao('p');
+            let modo = self.calculadora_piso.modo;
+            if modo != ModoRevestimento::Laminado {
+                let rotulo = if modo == ModoRevestimento::Parede {
+                    "Calcular Revestimento"
+                } else {
+                    "Calcular Piso"
+                };
+                if ui.checkbox(&mut self.calculadora_piso.calcular_piso, rotulo).clicked() {
+                    self.calculadora_piso.validar_selecao('p');
+                }
+                if ui
+                    .checkbox(&mut self.calculadora_piso.calcular_argamassa, "Calcular Argamassa")
+                    .clicked()
+                {
+                    self.calculadora_piso.validar_selecao('a');
+                }
+                if ui
+                    .checkbox(&mut self.calculadora_piso.calcular_rejunte, "Calcular Rejunte")
+                    .clicked()
+                {
+                    self.calculadora_piso.validar_selecao('r');
+                }
+                ui.checkbox(&mut self.calculadora_piso.calcular_espacadores, "Calcular Espaçadores");
             }
-            if ui
-                .checkbox(&mut self.calculadora_piso.calcular_argamassa, "Calcular Argamassa")
-                .clicked()
-            {
-                self.calculadora_piso.validar_selecao('a');
+            if modo != ModoRevestimento::Parede {
+                ui.checkbox(&mut self.calculadora_piso.calcular_rodape, "Calcular Rodapé");
+                ui.checkbox(&mut self.calculadora_piso.calcular_contrapiso, "Calcular Contrapiso");
             }
-            if ui
-                .checkbox(&mut self.calculadora_piso.calcular_rejunte, "Calcular Rejunte")
-                .clicked()
-            {
-                self.calculadora_piso.validar_selecao('r');
+            if modo == ModoRevestimento::Piso {
+                ui.checkbox(
+                    &mut self.calculadora_piso.calcular_impermeabilizacao,
+                    "Calcular Impermeabilização",
+                );
             }
         });
 
@@ -486,6 +1842,10 @@ impl CalculadoraConstrucao {
         // Mostrar ambientes sempre
         let mut ambiente_para_remover = None;
         let ambientes_len = self.calculadora_piso.ambientes.len();
+        let usa_rodape = self.calculadora_piso.usa_rodape();
+        let impermeabilizacao = self.calculadora_piso.usa_impermeabilizacao();
+        let rejunte = self.calculadora_piso.usa_rejunte();
+        let laminado = self.calculadora_piso.modo == ModoRevestimento::Laminado;
 
         egui::ScrollArea::vertical()
             .id_source("piso_ambientes_scroll")
@@ -499,24 +1859,125 @@ impl CalculadoraConstrucao {
                         );
                         ui.add_space(5.0);
 
+                        let modo_parede = self.calculadora_piso.modo == ModoRevestimento::Parede;
+
+                        if modo_parede {
+                            let mut parede_para_remover = None;
+                            let paredes_len = ambiente.paredes.len();
+
+                            for (indice, parede) in ambiente.paredes.iter_mut().enumerate() {
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("Parede {}:", indice + 1));
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut parede.comprimento)
+                                            .desired_width(70.0)
+                                            .hint_text("Compr. (m)"),
+                                    );
+                                    ui.label("x");
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut parede.altura)
+                                            .desired_width(70.0)
+                                            .hint_text("Altura (m)"),
+                                    );
+                                    ui.label("Aberturas:");
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut parede.aberturas)
+                                            .desired_width(160.0)
+                                            .hint_text("Ex: 1.20x1.00; 0.80x2.10"),
+                                    )
+                                        .on_hover_text("Janelas e portas descontadas da parede, como largura x altura (m).");
+                                    if ui.button("Remover").clicked() && paredes_len > 1 {
+                                        parede_para_remover = Some(indice);
+                                    }
+                                });
+                            }
+
+                            if let Some(indice) = parede_para_remover {
+                                ambiente.paredes.remove(indice);
+                            }
+
+                            if ui.button("Adicionar Parede").clicked() {
+                                ambiente.paredes.push(Parede::default());
+                            }
+
+                            ui.add_space(5.0);
+                        }
+
                         egui::Grid::new(format!("piso_ambiente_grid_{}", index))
                             .num_columns(2)
                             .spacing([10.0, 10.0])
                             .min_col_width(ui.available_width() / 2.0 - 20.0)
                             .show(ui, |ui| {
-                                ui.label("Largura (m):");
-                                ui.add(
-                                    egui::TextEdit::singleline(&mut ambiente.largura)
-                                        .desired_width(ui.available_width() / 2.0 - 20.0),
-                                );
-                                ui.end_row();
+                                if !modo_parede {
+                                    ui.label("Largura (m):");
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut ambiente.largura)
+                                            .desired_width(ui.available_width() / 2.0 - 20.0),
+                                    );
+                                    ui.end_row();
 
-                                ui.label("Comprimento (m):");
-                                ui.add(
-                                    egui::TextEdit::singleline(&mut ambiente.comprimento)
-                                        .desired_width(ui.available_width() / 2.0 - 20.0),
-                                );
-                                ui.end_row();
+                                    ui.label("Comprimento (m):");
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut ambiente.comprimento)
+                                            .desired_width(ui.available_width() / 2.0 - 20.0),
+                                    );
+                                    ui.end_row();
+                                }
+
+                                if usa_rodape || impermeabilizacao || laminado {
+                                    ui.label("Vãos de porta (m):");
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut ambiente.portas)
+                                            .desired_width(ui.available_width() / 2.0 - 20.0)
+                                            .hint_text("Ex: 0.80; 0.70"),
+                                    );
+                                    ui.end_row();
+                                }
+
+                                if rejunte {
+                                    ui.label("Cor do rejunte:");
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut ambiente.cor_rejunte)
+                                            .desired_width(ui.available_width() / 2.0 - 20.0)
+                                            .hint_text("Ex: Cinza Platina"),
+                                    );
+                                    ui.end_row();
+                                }
+
+                                if impermeabilizacao {
+                                    ui.label("Impermeabilizar:");
+                                    ui.checkbox(&mut ambiente.impermeabilizar, "Área molhada");
+                                    ui.end_row();
+
+                                    if ambiente.impermeabilizar {
+                                        ui.label("Parede do box (m):");
+                                        ui.add(
+                                            egui::TextEdit::singleline(&mut ambiente.perimetro_box)
+                                                .desired_width(ui.available_width() / 2.0 - 20.0)
+                                                .hint_text("Ex: 2.60"),
+                                        )
+                                            .on_hover_text("Comprimento de parede dentro do box, impermeabilizado até a altura do box.");
+                                        ui.end_row();
+                                    }
+                                }
+
+                                let produtos = &self.calculadora_piso.produtos;
+                                if !produtos.is_empty() && !laminado {
+                                    ui.label("Revestimento:");
+                                    let selecionado = match ambiente.produto {
+                                        Some(p) if p < produtos.len() => produtos[p].nome.as_str(),
+                                        _ => "Dados Gerais",
+                                    };
+                                    egui::ComboBox::from_id_source(format!("produto_piso_{}", index))
+                                        .selected_text(selecionado)
+                                        .show_ui(ui, |ui| {
+                                            ui.selectable_value(&mut ambiente.produto, None, "Dados Gerais");
+                                            for (indice, produto) in produtos.iter().enumerate() {
+                                                ui.selectable_value(&mut ambiente.produto, Some(indice), &produto.nome);
+                                            }
+                                        });
+                                    ui.end_row();
+                                }
                             });
 
                         ui.add_space(5.0);
@@ -546,35 +2007,509 @@ impl CalculadoraConstrucao {
 
         ui.add_space(10.0);
 
-        // **Alteração aplicada aqui**
-        if self.calculadora_piso.calcular_piso {
+        let mut catalogo_alterado = false;
+        ui.collapsing("Catálogo de Revestimentos", |ui| {
+            let piso = &mut self.calculadora_piso;
+            let mut produto_para_remover = None;
+            let mut produto_para_usar = None;
+
+            for (indice, produto) in piso.produtos.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{} - {} x {} x {} mm, {} m² e {} peças por caixa{}",
+                        produto.nome,
+                        produto.largura,
+                        produto.comprimento,
+                        produto.espessura,
+                        produto.area_caixa,
+                        produto.pecas_caixa,
+                        if produto.porcelanato { " (porcelanato)" } else { "" }
+                    ));
+                    if ui.button("Usar nos Dados Gerais").clicked() {
+                        produto_para_usar = Some(indice);
+                    }
+                    if ui.button("Remover").clicked() {
+                        produto_para_remover = Some(indice);
+                    }
+                });
+            }
+
+            if let Some(indice) = produto_para_usar {
+                piso.usar_produto(indice);
+            }
+            if let Some(indice) = produto_para_remover {
+                piso.remover_produto(indice);
+                catalogo_alterado = true;
+            }
+
+            ui.add_space(5.0);
+
+            egui::Grid::new("catalogo_piso_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .min_col_width(100.0)
+                .show(ui, |ui| {
+                    let novo = &mut piso.novo_produto;
+                    for (rotulo, campo) in [
+                        ("Nome:", &mut novo.nome),
+                        ("Largura (mm):", &mut novo.largura),
+                        ("Comprimento (mm):", &mut novo.comprimento),
+                        ("Espessura (mm):", &mut novo.espessura),
+                        ("Área por caixa (m²):", &mut novo.area_caixa),
+                        ("Peças por caixa:", &mut novo.pecas_caixa),
+                        ("Peso por caixa (kg, opcional):", &mut novo.peso_caixa),
+                        ("Preço por caixa (R$, opcional):", &mut novo.preco_caixa),
+                    ] {
+                        ui.label(rotulo);
+                        ui.add(egui::TextEdit::singleline(campo).desired_width(150.0));
+                        ui.end_row();
+                    }
+
+                    ui.label("Porcelanato:");
+                    ui.checkbox(&mut novo.porcelanato, "");
+                    ui.end_row();
+                });
+
+            ui.add_space(5.0);
+
             ui.horizontal(|ui| {
-                ui.label("Área da caixa (m²):");
-                ui.add(
-                    egui::TextEdit::singleline(&mut self.calculadora_piso.area_caixa)
-                        .desired_width(100.0),
-                );
+                if ui.button("Adicionar ao Catálogo").clicked() && piso.adicionar_produto() {
+                    catalogo_alterado = true;
+                }
+                ui.label(&piso.mensagem_catalogo);
             });
+        });
 
-            ui.add_space(10.0);
+        if catalogo_alterado {
+            self.salvar_catalogo_piso();
         }
 
-        if self.calculadora_piso.calcular_argamassa {
-            ui.add_space(10.0);
-            ui.label("Método de aplicação da argamassa:");
-            ui.horizontal(|ui| {
-                if ui
-                    .radio_value(
-                        &mut self.calculadora_piso.application_method,
-                        ApplicationMethod::SingleSided,
-                        "Aplicação Simples",
-                    )
-                    .clicked()
-                {
-                    self.atualizar_fator_argamassa();
-                }
-                if ui
-                    .radio_value(
+        ui.add_space(10.0);
+
+        // **Alteração aplicada aqui**
+        if self.calculadora_piso.usa_piso() {
+            if !self.calculadora_piso.usa_paginacao() {
+                ui.horizontal(|ui| {
+                    ui.label("Área da caixa (m²):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.calculadora_piso.area_caixa)
+                            .desired_width(100.0),
+                    );
+                });
+                ui.add_space(5.0);
+            }
+
+            if self.calculadora_piso.modo == ModoRevestimento::Piso
+                && ui
+                    .checkbox(
+                        &mut self.calculadora_piso.simular_paginacao,
+                        "Simular Paginação (peças inteiras, cortes e sobras)",
+                    )
+                    .on_hover_text("Distribui as peças a partir do canto ou do centro de cada ambiente, conforme o início da paginação, e conta as caixas pelas peças por caixa.")
+                    .clicked()
+            {
+                self.calculadora_piso.sugerir_perda();
+            }
+
+            if self.calculadora_piso.usa_paginacao() {
+                ui.horizontal(|ui| {
+                    ui.label("Peças por caixa:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.calculadora_piso.pecas_caixa)
+                            .desired_width(100.0),
+                    );
+                });
+            }
+
+            ui.add_space(5.0);
+
+            egui::Grid::new("politica_caixas_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .min_col_width(100.0)
+                .show(ui, |ui| {
+                    let piso = &mut self.calculadora_piso;
+
+                    ui.label("Perda (%):");
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut piso.percentual_perda)
+                                .desired_width(100.0),
+                        )
+                            .on_hover_text("Por área: perda por cortes conforme o padrão e o tamanho da peça.\nNa paginação os cortes já são contados, então fica só a quebra.");
+                        if ui.button("Sugerir").clicked() {
+                            piso.sugerir_perda();
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Caixas de reserva:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut piso.caixas_reserva)
+                            .desired_width(100.0),
+                    )
+                        .on_hover_text("Caixas inteiras do mesmo lote guardadas para reparos futuros.");
+                    ui.end_row();
+
+                    ui.label("Caixas por palete (opcional):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut piso.caixas_palete)
+                            .desired_width(100.0),
+                    )
+                        .on_hover_text("Pedidos a partir de um palete são arredondados para paletes fechados.");
+                    ui.end_row();
+                });
+
+            ui.add_space(10.0);
+        }
+
+        if self.calculadora_piso.usa_piso()
+            || self.calculadora_piso.usa_rejunte()
+            || self.calculadora_piso.usa_argamassa()
+            || self.calculadora_piso.usa_espacadores()
+            || (self.calculadora_piso.usa_rodape() && !laminado)
+        {
+            ui.label("Dados do Revestimento:");
+
+            let mut padrao_alterado = false;
+            egui::Grid::new("revestimento_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .min_col_width(ui.available_width() / 2.0 - 20.0)
+                .show(ui, |ui| {
+                    ui.label("Largura do Revestimento (mm):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.calculadora_piso.tile_width)
+                            .desired_width(ui.available_width() / 2.0 - 20.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Comprimento do Revestimento (mm):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.calculadora_piso.tile_length)
+                            .desired_width(ui.available_width() / 2.0 - 20.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Espaçamento das Juntas (mm):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.calculadora_piso.joint_spacing)
+                            .desired_width(ui.available_width() / 2.0 - 20.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Porcelanato:");
+                    ui.checkbox(&mut self.calculadora_piso.porcelanato, "")
+                        .on_hover_text("Define a argamassa recomendada; acima de 60x60 exige colagem dupla.");
+                    ui.end_row();
+
+                    ui.label("Padrão de Assentamento:");
+                    let padrao = &mut self.calculadora_piso.padrao_assentamento;
+                    egui::ComboBox::from_id_source("padrao_assentamento")
+                        .selected_text(padrao.nome())
+                        .show_ui(ui, |ui| {
+                            for opcao in [
+                                PadraoAssentamento::Reto,
+                                PadraoAssentamento::AmarracaoMeia,
+                                PadraoAssentamento::AmarracaoTerco,
+                                PadraoAssentamento::Diagonal,
+                                PadraoAssentamento::EspinhaDePeixe,
+                            ] {
+                                if ui.selectable_value(padrao, opcao, opcao.nome()).clicked() {
+                                    padrao_alterado = true;
+                                }
+                            }
+                        });
+                    ui.end_row();
+
+                    if self.calculadora_piso.modo == ModoRevestimento::Piso {
+                        ui.label("Início da Paginação:");
+                        ui.horizontal(|ui| {
+                            for inicio in [InicioPaginacao::Canto, InicioPaginacao::Centro] {
+                                ui.radio_value(
+                                    &mut self.calculadora_piso.inicio_paginacao,
+                                    inicio,
+                                    inicio.nome(),
+                                );
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+
+            if padrao_alterado {
+                self.calculadora_piso.sugerir_perda();
+            }
+
+            if self.calculadora_piso.modo == ModoRevestimento::Piso {
+                ui.add_space(5.0);
+                ui.collapsing("Visualizar Paginação", |ui| {
+                    let piso = &mut self.calculadora_piso;
+                    piso.atualizar_desenhos_paginacao();
+                    for (index, (_, desenho)) in piso.desenhos_paginacao.iter().enumerate() {
+                        ui.label(format!("Ambiente {}:", index + 1));
+                        match desenho {
+                            Ok(formas) => desenhar_formas(ui, formas),
+                            Err(mensagem) => {
+                                ui.label(RichText::new(mensagem).color(Color32::GRAY));
+                            }
+                        }
+                        ui.add_space(10.0);
+                    }
+                });
+            }
+
+            ui.add_space(10.0);
+        }
+
+        if self.calculadora_piso.usa_impermeabilizacao() {
+            ui.label("Impermeabilização:");
+            ui.horizontal(|ui| {
+                for produto in [Impermeabilizante::MantaLiquida, Impermeabilizante::Cimenticia] {
+                    if ui
+                        .radio_value(&mut self.calculadora_piso.impermeabilizante, produto, produto.nome())
+                        .clicked()
+                    {
+                        self.calculadora_piso.consumo_impermeabilizante =
+                            produto.consumo_padrao().to_string();
+                    }
+                }
+            });
+
+            egui::Grid::new("impermeabilizacao_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .min_col_width(100.0)
+                .show(ui, |ui| {
+                    let piso = &mut self.calculadora_piso;
+
+                    ui.label(format!(
+                        "Consumo por demão ({}/m²):",
+                        piso.impermeabilizante.unidade()
+                    ));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut piso.consumo_impermeabilizante)
+                            .desired_width(100.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Demãos:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut piso.demaos_impermeabilizante)
+                            .desired_width(100.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Subida nas paredes (m):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut piso.altura_subida)
+                            .desired_width(100.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Altura no box (m):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut piso.altura_box)
+                            .desired_width(100.0),
+                    );
+                    ui.end_row();
+                });
+
+            ui.add_space(10.0);
+        }
+
+        if laminado {
+            ui.label("Piso Laminado/Vinílico:");
+
+            egui::Grid::new("laminado_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .min_col_width(100.0)
+                .show(ui, |ui| {
+                    let piso = &mut self.calculadora_piso;
+
+                    ui.label("Comprimento da régua (mm):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut piso.comprimento_regua)
+                            .desired_width(100.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Largura da régua (mm):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut piso.largura_regua)
+                            .desired_width(100.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Área por caixa (m²):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut piso.area_caixa_laminado)
+                            .desired_width(100.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Deslocamento mínimo (cm):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut piso.deslocamento_regua)
+                            .desired_width(100.0),
+                    )
+                        .on_hover_text("Distância mínima entre as emendas de fileiras vizinhas; também é o menor pedaço de régua aceito.");
+                    ui.end_row();
+
+                    ui.label("Área do rolo de manta (m²):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut piso.area_rolo_manta)
+                            .desired_width(100.0),
+                    )
+                        .on_hover_text("Deixe em branco para réguas com manta incorporada.");
+                    ui.end_row();
+
+                    ui.label("Comprimento do perfil de transição (m):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut piso.comprimento_perfil_transicao)
+                            .desired_width(100.0),
+                    );
+                    ui.end_row();
+                });
+
+            ui.add_space(10.0);
+        }
+
+        if self.calculadora_piso.usa_contrapiso() {
+            ui.label("Contrapiso:");
+
+            egui::Grid::new("contrapiso_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .min_col_width(100.0)
+                .show(ui, |ui| {
+                    let piso = &mut self.calculadora_piso;
+
+                    ui.label("Espessura média (cm):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut piso.espessura_contrapiso)
+                            .desired_width(100.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Traço (Cimento:Areia):");
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut piso.traco_cimento)
+                                .desired_width(40.0),
+                        );
+                        ui.label(":");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut piso.traco_areia)
+                                .desired_width(40.0),
+                        );
+                    });
+                    ui.end_row();
+                });
+
+            ui.add_space(10.0);
+        }
+
+        if self.calculadora_piso.usa_rodape() {
+            ui.label("Rodapé:");
+            if !laminado {
+                ui.horizontal(|ui| {
+                    ui.radio_value(
+                        &mut self.calculadora_piso.tipo_rodape,
+                        TipoRodape::Ceramico,
+                        "Cortado das Placas",
+                    );
+                    ui.radio_value(
+                        &mut self.calculadora_piso.tipo_rodape,
+                        TipoRodape::Mdf,
+                        "Régua Pronta (MDF)",
+                    );
+                });
+            }
+
+            egui::Grid::new("rodape_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .min_col_width(100.0)
+                .show(ui, |ui| {
+                    let piso = &mut self.calculadora_piso;
+                    match piso.tipo_rodape_aplicado() {
+                        TipoRodape::Ceramico => {
+                            ui.label("Altura da faixa (cm):");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut piso.altura_rodape)
+                                    .desired_width(100.0),
+                            )
+                                .on_hover_text("As faixas são cortadas ao longo do comprimento das placas do piso de cada ambiente.");
+                            ui.end_row();
+
+                            // Na paginação o campo já aparece junto do piso
+                            if !piso.usa_paginacao() {
+                                ui.label("Peças por caixa:");
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut piso.pecas_caixa)
+                                        .desired_width(100.0)
+                                        .hint_text("Opcional"),
+                                )
+                                    .on_hover_text("Sem este valor o rodapé é contado só em placas.");
+                                ui.end_row();
+                            }
+                        }
+                        TipoRodape::Mdf => {
+                            ui.label("Comprimento da régua (m):");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut piso.comprimento_regua_rodape)
+                                    .desired_width(100.0),
+                            );
+                            ui.end_row();
+
+                            ui.label("Fixação:");
+                            ui.horizontal(|ui| {
+                                ui.radio_value(&mut piso.fixacao_rodape, FixacaoRodape::Pregos, "Pregos");
+                                ui.radio_value(&mut piso.fixacao_rodape, FixacaoRodape::Cola, "Cola");
+                            });
+                            ui.end_row();
+                        }
+                    }
+                });
+
+            ui.add_space(10.0);
+        }
+
+        if self.calculadora_piso.usa_argamassa() {
+            ui.add_space(10.0);
+            ui.label("Local de assentamento:");
+            ui.horizontal(|ui| {
+                for local in [
+                    LocalAssentamento::Interno,
+                    LocalAssentamento::Externo,
+                    LocalAssentamento::Piscina,
+                    LocalAssentamento::Fachada,
+                ] {
+                    ui.radio_value(
+                        &mut self.calculadora_piso.local_assentamento,
+                        local,
+                        local.nome(),
+                    );
+                }
+            });
+
+            ui.add_space(10.0);
+            ui.label("Método de aplicação da argamassa:");
+            ui.horizontal(|ui| {
+                if ui
+                    .radio_value(
+                        &mut self.calculadora_piso.application_method,
+                        ApplicationMethod::SingleSided,
+                        "Aplicação Simples",
+                    )
+                    .clicked()
+                {
+                    self.atualizar_fator_argamassa();
+                }
+                if ui
+                    .radio_value(
                         &mut self.calculadora_piso.application_method,
                         ApplicationMethod::DoubleSided,
                         "Colagem Dupla",
@@ -600,7 +2535,7 @@ impl CalculadoraConstrucao {
             });
         }
 
-        if self.calculadora_piso.calcular_rejunte {
+        if self.calculadora_piso.usa_rejunte() {
             ui.add_space(10.0);
             ui.label("Dados do Revestimento para o Rejunte:");
 
@@ -609,20 +2544,6 @@ impl CalculadoraConstrucao {
                 .spacing([10.0, 10.0])
                 .min_col_width(ui.available_width() / 2.0 - 20.0)
                 .show(ui, |ui| {
-                    ui.label("Largura do Revestimento (mm):");
-                    ui.add(
-                        egui::TextEdit::singleline(&mut self.calculadora_piso.tile_width)
-                            .desired_width(ui.available_width() / 2.0 - 20.0),
-                    );
-                    ui.end_row();
-
-                    ui.label("Comprimento do Revestimento (mm):");
-                    ui.add(
-                        egui::TextEdit::singleline(&mut self.calculadora_piso.tile_length)
-                            .desired_width(ui.available_width() / 2.0 - 20.0),
-                    );
-                    ui.end_row();
-
                     ui.label("Espessura do Revestimento (mm):");
                     ui.add(
                         egui::TextEdit::singleline(&mut self.calculadora_piso.tile_thickness)
@@ -630,13 +2551,6 @@ impl CalculadoraConstrucao {
                     );
                     ui.end_row();
 
-                    ui.label("Espaçamento das Juntas (mm):");
-                    ui.add(
-                        egui::TextEdit::singleline(&mut self.calculadora_piso.joint_spacing)
-                            .desired_width(ui.available_width() / 2.0 - 20.0),
-                    );
-                    ui.end_row();
-
                     ui.label("Coeficiente de Rejunte:");
                     ui.horizontal(|ui| {
                         ui.add(
@@ -650,6 +2564,20 @@ impl CalculadoraConstrucao {
                         }
                     });
                     ui.end_row();
+
+                    ui.label("Preço por embalagem (R$):");
+                    ui.horizontal(|ui| {
+                        for (tamanho, preco) in EMBALAGENS_REJUNTE
+                            .iter()
+                            .zip(self.calculadora_piso.precos_rejunte.iter_mut())
+                        {
+                            ui.label(format!("{} kg:", tamanho));
+                            ui.add(egui::TextEdit::singleline(preco).desired_width(60.0));
+                        }
+                    })
+                        .response
+                        .on_hover_text("Opcional. Com preços, as embalagens são combinadas pelo menor custo; embalagens sem preço não são usadas.");
+                    ui.end_row();
                 });
         }
 
@@ -706,6 +2634,8 @@ impl CalculadoraConstrucao {
             }
             ApplicationMethod::DoubleSided => {
                 self.config.fator_argamassa_dupla = self.calculadora_piso.argamassa_fator.clone();
+                self.calculadora_piso.fator_colagem_dupla =
+                    self.calculadora_piso.argamassa_fator.clone();
             }
         }
         confy::store("calculadora_construcao", None, &self.config).unwrap();
@@ -716,6 +2646,11 @@ impl CalculadoraConstrucao {
         confy::store("calculadora_construcao", None, &self.config).unwrap();
     }
 
+    fn salvar_catalogo_piso(&mut self) {
+        self.config.produtos_piso = self.calculadora_piso.produtos.clone();
+        confy::store("calculadora_construcao", None, &self.config).unwrap();
+    }
+
     fn mostrar_calculadora_materiais(&mut self, ui: &mut egui::Ui) {
         ui.heading(
             RichText::new("Calculadora de Materiais")
@@ -944,6 +2879,12 @@ impl CalculadoraConstrucao {
                 DirecaoForro::MenorLado,
                 "Menor Lado",
             );
+            ui.radio_value(
+                &mut self.calculadora_forro.direcao_global,
+                DirecaoForro::Automatico,
+                "Automático",
+            )
+                .on_hover_text("Compara as duas direções em cada ambiente e escolhe a de menor sobra de PVC.");
         });
 
         ui.add_space(10.0);
@@ -1081,53 +3022,735 @@ impl CalculadoraPiso {
     fn calcular(&mut self) {
         self.resultado.clear();
 
+        let modo_parede = self.modo == ModoRevestimento::Parede;
         let mut area_total = Decimal::ZERO;
+        let mut areas_ambientes = Vec::new();
+        let mut dimensoes_ambientes = Vec::new();
+        let mut perimetros_rodape = Vec::new();
+        let mut portas_ambientes = Vec::new();
+        let mut detalhes_paredes = String::new();
+
+        for (index, ambiente) in self.ambientes.iter().enumerate() {
+            if modo_parede {
+                let (area_bruta, area_aberturas) = match Self::area_paredes(&ambiente.paredes) {
+                    Ok(v) => v,
+                    Err(erro) => {
+                        self.resultado = format!("Ambiente {}: {}", index + 1, erro);
+                        return;
+                    }
+                };
+                let area_liquida = (area_bruta - area_aberturas).max(Decimal::ZERO);
+
+                detalhes_paredes.push_str(&format!(
+                    "Ambiente {}: Paredes {:.2} m² - Aberturas {:.2} m² = {:.2} m²\n",
+                    index + 1,
+                    area_bruta,
+                    area_aberturas,
+                    area_liquida
+                ));
+                area_total += area_liquida;
+                areas_ambientes.push(area_liquida);
+                continue;
+            }
 
-        for ambiente in &self.ambientes {
             let largura = match parse_decimal(&ambiente.largura) {
-                Ok(v) => v,
-                Err(_) => {
+                Ok(v) if v > Decimal::ZERO => v,
+                _ => {
                     self.resultado = "Largura de ambiente inválida".to_string();
                     return;
                 }
             };
 
             let comprimento = match parse_decimal(&ambiente.comprimento) {
-                Ok(v) => v,
-                Err(_) => {
+                Ok(v) if v > Decimal::ZERO => v,
+                _ => {
                     self.resultado = "Comprimento de ambiente inválido".to_string();
                     return;
                 }
             };
 
+            let vaos = if self.usa_rodape()
+                || self.usa_impermeabilizacao()
+                || self.modo == ModoRevestimento::Laminado
+            {
+                match parse_lista_decimal(&ambiente.portas) {
+                    Some(v) => v,
+                    None => {
+                        self.resultado = "Vãos de porta inválidos".to_string();
+                        return;
+                    }
+                }
+            } else {
+                Vec::new()
+            };
+            let portas: Decimal = vaos.iter().sum();
+            portas_ambientes.push(vaos);
+
             area_total += largura * comprimento;
+            areas_ambientes.push(largura * comprimento);
+            dimensoes_ambientes.push((largura, comprimento));
+            perimetros_rodape.push((dec!(2) * (largura + comprimento) - portas).max(Decimal::ZERO));
         }
 
-        if self.calcular_piso {
-            let area_caixa = match parse_decimal(&self.area_caixa) {
-                Ok(v) => v,
-                Err(_) => {
-                    self.resultado = "Área da caixa inválida".to_string();
+        let simular_paginacao = self.usa_paginacao();
+        let mut joint_spacing = Decimal::ZERO;
+
+        if simular_paginacao || self.usa_rejunte() || self.usa_espacadores() {
+            joint_spacing = match parse_decimal(&self.joint_spacing) {
+                Ok(v) if v >= Decimal::ZERO => v,
+                _ => {
+                    self.resultado = "Espaçamento das juntas inválido".to_string();
+                    return;
+                }
+            };
+        }
+
+        let mut perda = Decimal::ZERO;
+        let mut caixas_reserva = 0;
+        let mut caixas_palete = None;
+
+        if self.usa_piso() {
+            perda = match parse_decimal(&self.percentual_perda) {
+                Ok(v) if v >= Decimal::ZERO => v,
+                _ => {
+                    self.resultado = "Percentual de perda inválido".to_string();
+                    return;
+                }
+            };
+
+            if !self.caixas_reserva.trim().is_empty() {
+                caixas_reserva = match self.caixas_reserva.trim().parse::<u32>() {
+                    Ok(v) => v,
+                    Err(_) => {
+                        self.resultado = "Caixas de reserva inválido".to_string();
+                        return;
+                    }
+                };
+            }
+
+            if !self.caixas_palete.trim().is_empty() {
+                caixas_palete = match self.caixas_palete.trim().parse::<u32>() {
+                    Ok(v) if v > 0 => Some(v),
+                    _ => {
+                        self.resultado = "Caixas por palete inválido".to_string();
+                        return;
+                    }
+                };
+            }
+        }
+
+        // Caixas a comprar a partir da quantidade líquida (em caixas, sem arredondar)
+        let politica = |caixas_liquidas: Decimal| {
+            let caixas = (caixas_liquidas * (Decimal::ONE + perda / dec!(100)))
+                .ceil()
+                .to_u32()
+                .unwrap_or(0);
+            let mut linhas = format!("Perda Aplicada: {:.1}%\n", perda);
+            let mut total = caixas + caixas_reserva;
+            if caixas_reserva > 0 {
+                linhas.push_str(&format!("Caixas de Reserva (mesmo lote): {}\n", caixas_reserva));
+            }
+            if let Some(palete) = caixas_palete {
+                if total >= palete {
+                    let paletes = total.div_ceil(palete);
+                    linhas.push_str(&format!(
+                        "Arredondado para {} paletes de {} caixas (+{} caixas)\n",
+                        paletes,
+                        palete,
+                        paletes * palete - total
+                    ));
+                    total = paletes * palete;
+                }
+            }
+            (total, linhas)
+        };
+
+        // Ambientes agrupados pelo revestimento (produto do catálogo ou dados gerais)
+        let mut grupos: Vec<Option<usize>> = Vec::new();
+        let mut grupo_ambiente = Vec::new();
+        for ambiente in &self.ambientes {
+            let produto = ambiente.produto.filter(|p| *p < self.produtos.len());
+            let grupo = match grupos.iter().position(|g| *g == produto) {
+                Some(g) => g,
+                None => {
+                    grupos.push(produto);
+                    grupos.len() - 1
+                }
+            };
+            grupo_ambiente.push(grupo);
+        }
+
+        let mut revestimentos = Vec::new();
+        for produto in &grupos {
+            let revestimento = match produto {
+                Some(p) => self.produtos[*p].revestimento(),
+                None => self.revestimento_geral(simular_paginacao),
+            };
+            match revestimento {
+                Ok(r) => revestimentos.push(r),
+                Err(erro) => {
+                    self.resultado = erro;
+                    return;
+                }
+            }
+        }
+
+        // Só identifica o revestimento no relatório quando o catálogo está em uso
+        let identificar = grupos.iter().any(|g| g.is_some());
+        let cabecalho = |revestimento: &RevestimentoPiso| {
+            if identificar {
+                format!("Revestimento: {}\n", revestimento.nome)
+            } else {
+                String::new()
+            }
+        };
+        let custos = |revestimento: &RevestimentoPiso, caixas: Decimal| {
+            let mut linhas = String::new();
+            if let Some(peso) = revestimento.peso_caixa {
+                linhas.push_str(&format!("Peso Total: {:.1} kg\n", caixas * peso));
+            }
+            if let Some(preco) = revestimento.preco_caixa {
+                linhas.push_str(&format!("Custo Estimado: R$ {:.2}\n", caixas * preco));
+            }
+            linhas
+        };
+
+        let mut paginacoes = Vec::new();
+
+        let juntas_pela_formula = modo_parede || self.padrao_assentamento.juntas_pela_formula();
+        if simular_paginacao || (self.usa_rejunte() && !juntas_pela_formula) {
+            for (index, ((largura, comprimento), grupo)) in
+                dimensoes_ambientes.iter().zip(&grupo_ambiente).enumerate()
+            {
+                let revestimento = &revestimentos[*grupo];
+                let area_peca = revestimento.largura * revestimento.comprimento / dec!(1000000);
+                if largura * comprimento / area_peca > LIMITE_PECAS_PAGINACAO {
+                    self.resultado = format!(
+                        "Peças demais para paginar no ambiente {} (confira as medidas do revestimento em mm)",
+                        index + 1
+                    );
+                    return;
+                }
+                paginacoes.push(Self::paginar(
+                    *largura,
+                    *comprimento,
+                    revestimento.largura / dec!(1000),
+                    revestimento.comprimento / dec!(1000),
+                    joint_spacing / dec!(1000),
+                    self.padrao_assentamento,
+                    self.inicio_paginacao,
+                ));
+            }
+        }
+
+        if simular_paginacao {
+            let mut resultado = format!(
+                "Paginação do Piso:\nPadrão de Assentamento: {}\n",
+                self.padrao_assentamento.nome()
+            );
+
+            for (index, (paginacao, (largura, comprimento))) in
+                paginacoes.iter().zip(&dimensoes_ambientes).enumerate()
+            {
+                let revestimento = &revestimentos[grupo_ambiente[index]];
+                let area_peca = revestimento.largura * revestimento.comprimento / dec!(1000000);
+                let area_ambiente = largura * comprimento;
+                let pecas_ambiente = paginacao.inteiras + paginacao.pecas_para_cortes;
+                let perda = if area_ambiente.is_zero() {
+                    Decimal::ZERO
+                } else {
+                    (Decimal::from(pecas_ambiente) * area_peca - area_ambiente) / area_ambiente
+                        * dec!(100)
+                };
+
+                resultado.push_str(&format!(
+                    "Ambiente {}: {} inteiras, {} cortadas ({} peças abertas para cortes), \
+                    {} sobras reaproveitáveis, perda de {:.1}%\n",
+                    index + 1,
+                    paginacao.inteiras,
+                    paginacao.cortadas,
+                    paginacao.pecas_para_cortes,
+                    paginacao.sobras.len(),
+                    perda
+                ));
+            }
+
+            for (grupo, revestimento) in revestimentos.iter().enumerate() {
+                let mut area_grupo = Decimal::ZERO;
+                let mut inteiras = 0;
+                let mut cortadas = 0;
+                let mut pecas_para_cortes = 0;
+                let mut sobras = 0;
+
+                for (index, paginacao) in paginacoes.iter().enumerate() {
+                    if grupo_ambiente[index] == grupo {
+                        let (largura, comprimento) = dimensoes_ambientes[index];
+                        area_grupo += largura * comprimento;
+                        inteiras += paginacao.inteiras;
+                        cortadas += paginacao.cortadas;
+                        pecas_para_cortes += paginacao.pecas_para_cortes;
+                        sobras += paginacao.sobras.len();
+                    }
+                }
+
+                let total_pecas = inteiras + pecas_para_cortes;
+                let (caixas_necessarias, politica_texto) = politica(
+                    Decimal::from(total_pecas) / Decimal::from(revestimento.pecas_caixa),
+                );
+                let pecas_compradas = caixas_necessarias * revestimento.pecas_caixa;
+                let metragem_total = Decimal::from(pecas_compradas) * revestimento.largura
+                    * revestimento.comprimento
+                    / dec!(1000000);
+
+                resultado.push('\n');
+                resultado.push_str(&cabecalho(revestimento));
+                resultado.push_str(&format!(
+                    "Área Total a Cobrir: {:.2} m²\nPeças Inteiras: {}\nPeças Cortadas: {}\n\
+                    Peças Abertas para Cortes: {}\nSobras Reaproveitáveis: {}\nTotal de Peças: {}\n\
+                    Caixas Necessárias: {} ({} peças por caixa, {:.2} m²)\nPeças Sobrando nas Caixas: {}\n",
+                    area_grupo,
+                    inteiras,
+                    cortadas,
+                    pecas_para_cortes,
+                    sobras,
+                    total_pecas,
+                    caixas_necessarias,
+                    revestimento.pecas_caixa,
+                    metragem_total,
+                    pecas_compradas - total_pecas
+                ));
+                resultado.push_str(&politica_texto);
+                resultado.push_str(&custos(revestimento, Decimal::from(caixas_necessarias)));
+            }
+
+            resultado.push('\n');
+            self.resultado.push_str(&resultado);
+        } else if self.usa_piso() {
+            let mut resultado = if modo_parede {
+                format!("Cálculo de Revestimento de Parede:\n{}\n", detalhes_paredes)
+            } else {
+                String::from("Cálculo de Piso:\n")
+            };
+
+            for (grupo, revestimento) in revestimentos.iter().enumerate() {
+                let area_grupo: Decimal = areas_ambientes
+                    .iter()
+                    .zip(&grupo_ambiente)
+                    .filter(|(_, g)| **g == grupo)
+                    .map(|(area, _)| *area)
+                    .sum();
+
+                let (caixas, politica_texto) = politica(area_grupo / revestimento.area_caixa);
+                let caixas_necessarias = Decimal::from(caixas);
+                let metragem_total = caixas_necessarias * revestimento.area_caixa;
+                let sobra_estimada = metragem_total - area_grupo;
+
+                resultado.push_str(&cabecalho(revestimento));
+                resultado.push_str(&format!(
+                    "Área da Caixa: {:.2} m²\nÁrea Total a Cobrir: {:.2} m²\n",
+                    revestimento.area_caixa,
+                    area_grupo,
+                ));
+                resultado.push_str(&politica_texto);
+                resultado.push_str(&format!(
+                    "Caixas Necessárias: {} ({:.2} m²)\nSobra Estimada: {:.2} m²\n",
+                    caixas_necessarias,
+                    metragem_total,
+                    sobra_estimada
+                ));
+                resultado.push_str(&custos(revestimento, caixas_necessarias));
+                resultado.push('\n');
+            }
+
+            self.resultado.push_str(&resultado);
+        }
+
+        if self.modo == ModoRevestimento::Laminado {
+            let mut parametros = Vec::new();
+            for (valor, erro) in [
+                (&self.comprimento_regua, "Comprimento da régua inválido"),
+                (&self.largura_regua, "Largura da régua inválida"),
+                (&self.area_caixa_laminado, "Área da caixa inválida"),
+                (&self.deslocamento_regua, "Deslocamento mínimo inválido"),
+                (&self.comprimento_perfil_transicao, "Comprimento do perfil de transição inválido"),
+            ] {
+                match parse_decimal(valor) {
+                    Ok(v) if v > Decimal::ZERO => parametros.push(v),
+                    _ => {
+                        self.resultado = erro.to_string();
+                        return;
+                    }
+                }
+            }
+            let (regua_comprimento, regua_largura, area_caixa, deslocamento, perfil) = (
+                parametros[0],
+                parametros[1],
+                parametros[2],
+                parametros[3],
+                parametros[4],
+            );
+            let deslocamento = deslocamento * dec!(10);
+            if deslocamento >= regua_comprimento {
+                self.resultado = "Deslocamento mínimo maior que a régua".to_string();
+                return;
+            }
+
+            let area_rolo = if self.area_rolo_manta.trim().is_empty() {
+                None
+            } else {
+                match parse_decimal(&self.area_rolo_manta) {
+                    Ok(v) if v > Decimal::ZERO => Some(v),
+                    _ => {
+                        self.resultado = "Área do rolo de manta inválida".to_string();
+                        return;
+                    }
+                }
+            };
+
+            let mut resultado = format!(
+                "Cálculo de Piso Laminado/Vinílico:\nRégua: {} x {} mm\nDeslocamento Mínimo: {} cm\n",
+                regua_comprimento,
+                regua_largura,
+                deslocamento / dec!(10)
+            );
+
+            let mut reguas = 0;
+            let mut perfis = 0;
+            let mut vaos = 0;
+            for (index, ((largura, comprimento), portas)) in
+                dimensoes_ambientes.iter().zip(&portas_ambientes).enumerate()
+            {
+                let paginacao = Self::paginar_laminado(
+                    largura * dec!(1000) - dec!(2) * FOLGA_DILATACAO_LAMINADO,
+                    comprimento * dec!(1000) - dec!(2) * FOLGA_DILATACAO_LAMINADO,
+                    regua_comprimento,
+                    regua_largura,
+                    deslocamento,
+                );
+                reguas += paginacao.reguas;
+
+                let perfis_ambiente: u32 = portas
+                    .iter()
+                    .map(|vao| (vao / perfil).ceil().to_u32().unwrap_or(0))
+                    .sum();
+                perfis += perfis_ambiente;
+                vaos += portas.len();
+
+                resultado.push_str(&format!(
+                    "Ambiente {}: {} fileiras ({} com {:.0} mm), {} réguas, \
+                    {} cortes de início e {} de final\n",
+                    index + 1,
+                    paginacao.fileiras,
+                    if paginacao.bordas_equilibradas {
+                        "primeira e última"
+                    } else {
+                        "última"
+                    },
+                    paginacao.largura_borda,
+                    paginacao.reguas,
+                    paginacao.cortes_inicio,
+                    paginacao.cortes_final
+                ));
+            }
+
+            let area_reguas = Decimal::from(reguas) * regua_comprimento * regua_largura / dec!(1000000);
+            let caixas = (area_reguas / area_caixa).ceil();
+
+            resultado.push_str(&format!(
+                "Área Total: {:.2} m² (folga de dilatação de {} mm nas paredes)\n\
+                Réguas Necessárias: {} ({:.2} m²)\nCaixas Necessárias: {} ({:.2} m²)\n",
+                area_total,
+                FOLGA_DILATACAO_LAMINADO,
+                reguas,
+                area_reguas,
+                caixas,
+                caixas * area_caixa
+            ));
+            if let Some(area_rolo) = area_rolo {
+                resultado.push_str(&format!(
+                    "Rolos de Manta ({} m²): {}\n",
+                    area_rolo,
+                    (area_total * PERDA_MANTA_LAMINADO / area_rolo).ceil()
+                ));
+            }
+            resultado.push_str(&format!(
+                "Perfis de Transição de {:.2} m: {} ({} vãos de porta)\n\n",
+                perfil, perfis, vaos
+            ));
+
+            self.resultado.push_str(&resultado);
+        }
+
+        if self.usa_impermeabilizacao() {
+            let mut parametros = Vec::new();
+            for (valor, erro) in [
+                (&self.consumo_impermeabilizante, "Consumo do impermeabilizante inválido"),
+                (&self.demaos_impermeabilizante, "Número de demãos inválido"),
+                (&self.altura_subida, "Altura de subida inválida"),
+                (&self.altura_box, "Altura do box inválida"),
+            ] {
+                match parse_decimal(valor) {
+                    Ok(v) if v >= Decimal::ZERO => parametros.push(v),
+                    _ => {
+                        self.resultado = erro.to_string();
+                        return;
+                    }
+                }
+            }
+            let (consumo, demaos, altura_subida, altura_box) =
+                (parametros[0], parametros[1], parametros[2], parametros[3]);
+
+            let produto = self.impermeabilizante;
+            let mut resultado = format!(
+                "Cálculo de Impermeabilização:\nProduto: {} ({} demãos de {} {}/m²)\n",
+                produto.nome(),
+                demaos,
+                consumo,
+                produto.unidade()
+            );
+            let mut area_impermeabilizada = Decimal::ZERO;
+
+            for (index, ambiente) in self.ambientes.iter().enumerate() {
+                if !ambiente.impermeabilizar {
+                    continue;
+                }
+
+                let (largura, comprimento) = dimensoes_ambientes[index];
+                let perimetro_box = if ambiente.perimetro_box.trim().is_empty() {
+                    Decimal::ZERO
+                } else {
+                    match parse_decimal(&ambiente.perimetro_box) {
+                        Ok(v) if v >= Decimal::ZERO => v,
+                        _ => {
+                            self.resultado = "Parede do box inválida".to_string();
+                            return;
+                        }
+                    }
+                };
+
+                // O box sobe até a altura do box; o restante do contorno (sem as portas) até a subida
+                let perimetro_subida = (perimetros_rodape[index] - perimetro_box).max(Decimal::ZERO);
+                let area_piso = largura * comprimento;
+                let area_subida = perimetro_subida * altura_subida;
+                let area_box = perimetro_box * altura_box;
+                let area_ambiente = area_piso + area_subida + area_box;
+
+                resultado.push_str(&format!(
+                    "Ambiente {}: Piso {:.2} m² + Subida {:.2} m² + Box {:.2} m² = {:.2} m²\n",
+                    index + 1,
+                    area_piso,
+                    area_subida,
+                    area_box,
+                    area_ambiente
+                ));
+                area_impermeabilizada += area_ambiente;
+            }
+
+            let quantidade = area_impermeabilizada * consumo * demaos;
+            let embalagens = produto.embalagens();
+            let combinacao =
+                combinar_embalagens(quantidade, &embalagens.map(|tamanho| (tamanho, tamanho)));
+            let total_embalagens: Decimal = embalagens
+                .iter()
+                .zip(&combinacao)
+                .map(|(tamanho, n)| tamanho * Decimal::from(*n))
+                .sum();
+            let descricao: Vec<String> = embalagens
+                .iter()
+                .zip(&combinacao)
+                .filter(|(_, n)| **n > 0)
+                .map(|(tamanho, n)| format!("{} x {} {}", n, tamanho, produto.unidade()))
+                .collect();
+
+            resultado.push_str(&format!(
+                "Área Total Impermeabilizada: {:.2} m²\nConsumo: {:.1} {}\nEmbalagens: {} ({} {})\n\n",
+                area_impermeabilizada,
+                quantidade,
+                produto.unidade(),
+                if descricao.is_empty() {
+                    "nenhuma".to_string()
+                } else {
+                    descricao.join(" + ")
+                },
+                total_embalagens,
+                produto.unidade()
+            ));
+
+            self.resultado.push_str(&resultado);
+        }
+
+        if self.usa_contrapiso() {
+            let espessura = match parse_decimal(&self.espessura_contrapiso) {
+                Ok(v) if v > Decimal::ZERO => v,
+                _ => {
+                    self.resultado = "Espessura do contrapiso inválida".to_string();
                     return;
                 }
             };
 
-            let caixas_necessarias = (area_total / area_caixa).ceil();
-            let metragem_total = caixas_necessarias * area_caixa;
-            let sobra_estimada = metragem_total - area_total;
+            let volume_argamassa = area_total * espessura / dec!(100);
+            let volume_seco = volume_argamassa * FATOR_VOLUME_SECO_ARGAMASSA;
+
+            let (traco_cimento, traco_areia) =
+                match (parse_decimal(&self.traco_cimento), parse_decimal(&self.traco_areia)) {
+                    (Ok(c), Ok(a)) if c > Decimal::ZERO && a >= Decimal::ZERO => (c, a),
+                    _ => {
+                        self.resultado = "Traço do contrapiso inválido".to_string();
+                        return;
+                    }
+                };
+
+            // Mesmo cálculo da Calculadora de Materiais, com traço sem pedra
+            let materiais = CalculadoraMateriais {
+                volume_concreto: volume_seco.to_string(),
+                proporcao_cimento: traco_cimento.to_string(),
+                proporcao_areia: traco_areia.to_string(),
+                proporcao_pedra: "0".to_string(),
+                ..Default::default()
+            };
+
+            let (cimento, areia) = match materiais.calcular_por_volume() {
+                (_, Some(cimento), Some(areia), _) => (cimento, areia),
+                _ => {
+                    self.resultado = "Traço do contrapiso inválido".to_string();
+                    return;
+                }
+            };
+            let (areia_m3, areia_sacos) = CalculadoraMateriais::agregado_m3_e_sacos(areia);
 
             self.resultado.push_str(&format!(
-                "Cálculo de Piso:\nÁrea da Caixa: {:.2} m²\nÁrea Total a Cobrir: {:.2} m²\n\
-                Caixas Necessárias: {} ({:.2} m²)\nSobra Estimada: {:.2} m²\n\n",
-                area_caixa,
+                "Cálculo de Contrapiso:\nÁrea: {:.2} m²\nEspessura Média: {} cm\nTraço (Cimento:Areia): {}:{}\n\
+                Volume de Argamassa: {:.2} m³ (materiais secos: {:.2} m³)\n\
+                Cimento: {} sacos de {}kg\nAreia: {:.1} m³ ou {} sacos de {}kg\n\n",
                 area_total,
-                caixas_necessarias,
-                metragem_total,
-                sobra_estimada
+                espessura,
+                traco_cimento,
+                traco_areia,
+                volume_argamassa,
+                volume_seco,
+                CalculadoraMateriais::sacos_cimento(cimento),
+                PESO_SACO_CIMENTO,
+                areia_m3,
+                areia_sacos,
+                PESO_SACO_AREIA,
+            ));
+        }
+
+        if self.usa_rodape() {
+            let mut resultado = String::from("Cálculo de Rodapé:\n");
+
+            for (index, ((largura, comprimento), metros)) in
+                dimensoes_ambientes.iter().zip(&perimetros_rodape).enumerate()
+            {
+                resultado.push_str(&format!(
+                    "Ambiente {}: Perímetro {:.2} m - Portas {:.2} m = {:.2} m\n",
+                    index + 1,
+                    dec!(2) * (largura + comprimento),
+                    dec!(2) * (largura + comprimento) - metros,
+                    metros
+                ));
+            }
+
+            let metros_total: Decimal = perimetros_rodape.iter().sum();
+            resultado.push_str(&format!(
+                "Comprimento Total: {:.2} m (+{:.0}% de emendas e cantos)\n",
+                metros_total,
+                (PERDA_RODAPE - Decimal::ONE) * dec!(100)
             ));
+
+            match self.tipo_rodape_aplicado() {
+                TipoRodape::Ceramico => {
+                    let altura = match parse_decimal(&self.altura_rodape) {
+                        Ok(v) if v > Decimal::ZERO => v,
+                        _ => {
+                            self.resultado = "Altura do rodapé inválida".to_string();
+                            return;
+                        }
+                    };
+
+                    // Faixas cortadas ao longo do comprimento da placa do grupo
+                    for (grupo, revestimento) in revestimentos.iter().enumerate() {
+                        let metros_grupo: Decimal = perimetros_rodape
+                            .iter()
+                            .zip(&grupo_ambiente)
+                            .filter(|(_, g)| **g == grupo)
+                            .map(|(m, _)| *m)
+                            .sum();
+
+                        let faixas_por_placa =
+                            (revestimento.largura / (altura * dec!(10) + ESPESSURA_CORTE_RODAPE)).floor();
+                        if faixas_por_placa.is_zero() {
+                            self.resultado =
+                                "Altura do rodapé maior que a largura da placa".to_string();
+                            return;
+                        }
+
+                        let comprimento_faixa = revestimento.comprimento / dec!(1000);
+                        let faixas = (metros_grupo * PERDA_RODAPE / comprimento_faixa).ceil();
+                        let placas = (faixas / faixas_por_placa).ceil();
+
+                        resultado.push_str(&cabecalho(revestimento));
+                        resultado.push_str(&format!(
+                            "Faixas de {:.2} m x {} cm: {} ({} por placa)\nPlacas para Rodapé: {}\n",
+                            comprimento_faixa,
+                            altura,
+                            faixas,
+                            faixas_por_placa,
+                            placas
+                        ));
+                        if revestimento.pecas_caixa > 0 {
+                            resultado.push_str(&format!(
+                                "Caixas para Rodapé: {}\n",
+                                (placas / Decimal::from(revestimento.pecas_caixa)).ceil()
+                            ));
+                        } else {
+                            resultado.push_str(
+                                "Caixas para Rodapé: informe as peças por caixa para calcular\n",
+                            );
+                        }
+                    }
+
+                    let argamassa =
+                        metros_total * PERDA_RODAPE * altura / dec!(100) * CONSUMO_ARGAMASSA_RODAPE;
+                    resultado.push_str(&format!(
+                        "Argamassa para Rodapé: {:.1} kg\n",
+                        argamassa
+                    ));
+                }
+                TipoRodape::Mdf => {
+                    let comprimento_regua = match parse_decimal(&self.comprimento_regua_rodape) {
+                        Ok(v) if v > Decimal::ZERO => v,
+                        _ => {
+                            self.resultado = "Comprimento da régua de rodapé inválido".to_string();
+                            return;
+                        }
+                    };
+
+                    let reguas = (metros_total * PERDA_RODAPE / comprimento_regua).ceil();
+                    resultado.push_str(&format!(
+                        "Réguas de {:.2} m: {}\n",
+                        comprimento_regua, reguas
+                    ));
+
+                    match self.fixacao_rodape {
+                        FixacaoRodape::Pregos => resultado.push_str(&format!(
+                            "Pregos (1 a cada {:.0} cm): {}\n",
+                            ESPACAMENTO_PREGOS_RODAPE * dec!(100),
+                            (metros_total / ESPACAMENTO_PREGOS_RODAPE).ceil()
+                        )),
+                        FixacaoRodape::Cola => resultado.push_str(&format!(
+                            "Cartuchos de Cola: {}\n",
+                            (metros_total / RENDIMENTO_CARTUCHO_COLA).ceil()
+                        )),
+                    }
+                }
+            }
+
+            resultado.push('\n');
+            self.resultado.push_str(&resultado);
         }
 
-        if self.calcular_argamassa {
+        if self.usa_argamassa() {
             let fator = match parse_decimal(&self.argamassa_fator) {
                 Ok(v) => v,
                 Err(_) => {
@@ -1136,57 +3759,165 @@ impl CalculadoraPiso {
                 }
             };
 
-            let argamassa_kg = area_total * fator;
-            let sacos_necessarios = (argamassa_kg / dec!(20)).ceil();
+            let colagem_simples = self.application_method == ApplicationMethod::SingleSided;
+            let mut recomendacoes = String::new();
+            let mut tipos: Vec<(&str, Decimal)> = Vec::new();
+
+            for (grupo, revestimento) in revestimentos.iter().enumerate() {
+                let area_grupo: Decimal = areas_ambientes
+                    .iter()
+                    .zip(&grupo_ambiente)
+                    .filter(|(_, g)| **g == grupo)
+                    .map(|(area, _)| *area)
+                    .sum();
+                let (tipo, dupla) = self.local_assentamento.recomendar_argamassa(revestimento);
+
+                // Peças que exigem colagem dupla usam o fator da colagem dupla
+                let fator_grupo = if dupla && colagem_simples {
+                    match parse_decimal(&self.fator_colagem_dupla) {
+                        Ok(v) => v,
+                        Err(_) => {
+                            self.resultado = "Fator de colagem dupla inválido".to_string();
+                            return;
+                        }
+                    }
+                } else {
+                    fator
+                };
+                let kg = area_grupo * fator_grupo;
+
+                let porcelanato = if revestimento.porcelanato { " porcelanato" } else { "" };
+                let medidas = if revestimento.largura.is_zero() {
+                    format!("medidas não informadas{}, sem verificar colagem dupla", porcelanato)
+                } else {
+                    format!("{} x {} mm{}", revestimento.largura, revestimento.comprimento, porcelanato)
+                };
+                recomendacoes.push_str(&format!(
+                    "{}: {} -> Argamassa {}, {} ({:.2} kg/m²): {:.2} kg\n",
+                    revestimento.nome,
+                    medidas,
+                    tipo,
+                    if dupla || !colagem_simples {
+                        "colagem dupla"
+                    } else {
+                        "aplicação simples"
+                    },
+                    fator_grupo,
+                    kg
+                ));
+                match tipos.iter_mut().find(|(t, _)| *t == tipo) {
+                    Some((_, total)) => *total += kg,
+                    None => tipos.push((tipo, kg)),
+                }
+            }
+
+            let argamassa_kg: Decimal = tipos.iter().map(|(_, kg)| kg).sum();
+            let mut sacos_necessarios = Decimal::ZERO;
+            let mut sacos_texto = String::new();
+            for (tipo, kg) in &tipos {
+                let sacos = (kg / dec!(20)).ceil();
+                sacos_necessarios += sacos;
+                sacos_texto.push_str(&format!("Argamassa {}: {} sacos de 20kg\n", tipo, sacos));
+            }
 
             self.resultado.push_str(&format!(
-                "Cálculo de Argamassa:\nÁrea Total: {:.2} m²\nMétodo de Aplicação: {}\n\
-                Fator de Consumo: {:.2} kg/m²\nQuantidade de Argamassa Necessária: {:.2} kg\nSacos de 20kg Necessários: {}\n\n",
+                "Cálculo de Argamassa:\nÁrea Total: {:.2} m²\nLocal de Assentamento: {}\nMétodo de Aplicação: {}\n\
+                {}Quantidade de Argamassa Necessária: {:.2} kg\n{}Sacos de 20kg Necessários: {}\n\n",
                 area_total,
-                if self.application_method == ApplicationMethod::SingleSided {
+                self.local_assentamento.nome(),
+                if colagem_simples {
                     "Aplicação Simples"
                 } else {
                     "Colagem Dupla"
                 },
-                fator,
+                recomendacoes,
                 argamassa_kg,
+                sacos_texto,
                 sacos_necessarios,
             ));
         }
 
-        if self.calcular_rejunte {
-            let tile_width = match parse_decimal(&self.tile_width) {
-                Ok(v) => v,
-                Err(_) => {
-                    self.resultado = "Largura do revestimento inválida".to_string();
-                    return;
-                }
+        if self.usa_espacadores() {
+            let embalagens: Vec<(Decimal, Decimal)> =
+                EMBALAGENS_ESPACADORES.iter().map(|t| (*t, *t)).collect();
+            let pacotes = |unidades: Decimal| {
+                let combinacao = combinar_embalagens(unidades, &embalagens);
+                EMBALAGENS_ESPACADORES
+                    .iter()
+                    .zip(&combinacao)
+                    .filter(|(_, n)| **n > 0)
+                    .map(|(tamanho, n)| format!("{} pacote(s) de {}", n, tamanho))
+                    .collect::<Vec<_>>()
+                    .join(" + ")
             };
 
-            let tile_length = match parse_decimal(&self.tile_length) {
-                Ok(v) => v,
-                Err(_) => {
-                    self.resultado = "Comprimento do revestimento inválida".to_string();
-                    return;
-                }
-            };
+            let mut resultado = String::from("Cálculo de Espaçadores:\n");
+            for (grupo, revestimento) in revestimentos.iter().enumerate() {
+                let area_grupo: Decimal = areas_ambientes
+                    .iter()
+                    .zip(&grupo_ambiente)
+                    .filter(|(_, g)| **g == grupo)
+                    .map(|(area, _)| *area)
+                    .sum();
+                let pecas_m2 = dec!(1000000)
+                    / ((revestimento.largura + joint_spacing)
+                        * (revestimento.comprimento + joint_spacing));
+
+                resultado.push_str(&cabecalho(revestimento));
+                resultado.push_str(&format!(
+                    "Área: {:.2} m²\nPeças por m²: {:.2}\n",
+                    area_grupo, pecas_m2
+                ));
 
-            let tile_thickness = match parse_decimal(&self.tile_thickness) {
-                Ok(v) => v,
-                Err(_) => {
-                    self.resultado = "Espessura do revestimento inválida".to_string();
-                    return;
+                if revestimento.largura.max(revestimento.comprimento) >= LADO_MINIMO_NIVELADOR {
+                    // Cada peça leva clipes nos seus dois lados próprios; os outros dois
+                    // são contados pelas vizinhas
+                    let mut clipes_peca = (revestimento.largura / ESPACAMENTO_CLIPES_NIVELADOR).ceil()
+                        + (revestimento.comprimento / ESPACAMENTO_CLIPES_NIVELADOR).ceil();
+                    if self.modo == ModoRevestimento::Piso
+                        && self.padrao_assentamento.espacadores_por_peca() > Decimal::ONE
+                    {
+                        clipes_peca += Decimal::ONE; // encontro desencontrado das juntas
+                    }
+                    let clipes_m2 = pecas_m2 * clipes_peca * PERDA_ESPACADORES;
+                    let clipes = (area_grupo * clipes_m2).ceil();
+                    let cunhas = (clipes / REUSOS_CUNHA_NIVELADOR).ceil();
+
+                    resultado.push_str(&format!(
+                        "Sistema: Clipes Niveladores de {} mm com Cunhas\n\
+                        Clipes por m²: {:.2}\nClipes: {} ({})\nCunhas (reutilizadas {} vezes): {} ({})\n",
+                        joint_spacing,
+                        clipes_m2,
+                        clipes,
+                        pacotes(clipes),
+                        REUSOS_CUNHA_NIVELADOR,
+                        cunhas,
+                        pacotes(cunhas)
+                    ));
+                } else {
+                    let por_peca = if self.modo == ModoRevestimento::Piso {
+                        self.padrao_assentamento.espacadores_por_peca()
+                    } else {
+                        Decimal::ONE
+                    };
+                    let cruzetas_m2 = pecas_m2 * por_peca * PERDA_ESPACADORES;
+                    let cruzetas = (area_grupo * cruzetas_m2).ceil();
+
+                    resultado.push_str(&format!(
+                        "Sistema: Cruzetas de {} mm\nCruzetas por m²: {:.2}\nCruzetas: {} ({})\n",
+                        joint_spacing,
+                        cruzetas_m2,
+                        cruzetas,
+                        pacotes(cruzetas)
+                    ));
                 }
-            };
+            }
 
-            let joint_spacing = match parse_decimal(&self.joint_spacing) {
-                Ok(v) => v,
-                Err(_) => {
-                    self.resultado = "Espaçamento das juntas inválido".to_string();
-                    return;
-                }
-            };
+            resultado.push('\n');
+            self.resultado.push_str(&resultado);
+        }
 
+        if self.usa_rejunte() {
             let rejunte_coeficiente = match parse_decimal(&self.rejunte_coeficiente) {
                 Ok(v) => v,
                 Err(_) => {
@@ -1195,45 +3926,1289 @@ impl CalculadoraPiso {
                 }
             };
 
-            // Fórmula: kg/m² = (L+C) x E x J x Coef / (L x C)
-            let numerator =
-                (tile_width + tile_length) * tile_thickness * joint_spacing * rejunte_coeficiente;
-            let denominator = tile_width * tile_length;
-            let rejunte_por_m2 = numerator / denominator;
+            // Embalagens à venda: com algum preço informado, as sem preço ficam de fora
+            let mut embalagens = Vec::new();
+            let com_precos = self.precos_rejunte.iter().any(|p| !p.trim().is_empty());
+            for (tamanho, preco) in EMBALAGENS_REJUNTE.iter().zip(&self.precos_rejunte) {
+                if !com_precos {
+                    embalagens.push((*tamanho, *tamanho));
+                } else if !preco.trim().is_empty() {
+                    match parse_decimal(preco) {
+                        Ok(v) if v >= Decimal::ZERO => embalagens.push((*tamanho, v)),
+                        _ => {
+                            self.resultado = "Preço da embalagem de rejunte inválido".to_string();
+                            return;
+                        }
+                    }
+                }
+            }
+
+            // Fórmula: kg/m² = (L+C) x E x J x Coef / (L x C), onde (L+C)/(L x C) é o
+            // comprimento de junta por m². Nas paredes e nos padrões de juntas contínuas vale
+            // a fórmula direta; nos outros o comprimento vem da paginação do padrão.
+            let mut juntas_ambientes = Vec::new();
+            let mut rejunte_ambientes = Vec::new();
+            for (index, grupo) in grupo_ambiente.iter().enumerate() {
+                let revestimento = &revestimentos[*grupo];
+                let juntas = if juntas_pela_formula {
+                    areas_ambientes[index] * (revestimento.largura + revestimento.comprimento)
+                        * dec!(1000)
+                        / (revestimento.largura * revestimento.comprimento)
+                } else {
+                    paginacoes[index].comprimento_juntas
+                };
+                juntas_ambientes.push(juntas);
+                rejunte_ambientes.push(
+                    juntas * revestimento.espessura * joint_spacing * rejunte_coeficiente / dec!(1000)
+                        * dec!(1.05), // Acrescentar 5% de perda
+                );
+            }
+
+            let mut revestimentos_texto = String::new();
+            let comprimento_juntas: Decimal = juntas_ambientes.iter().sum();
+            let rejunte_total: Decimal = rejunte_ambientes.iter().sum();
 
-            let rejunte_total = rejunte_por_m2 * area_total * dec!(1.05); // Acrescentar 5% de perda
+            for revestimento in &revestimentos {
+                revestimentos_texto.push_str(&format!(
+                    "Revestimento: {}{:.0}mm x {:.0}mm x {:.0}mm\n",
+                    if identificar {
+                        format!("{} - ", revestimento.nome)
+                    } else {
+                        String::new()
+                    },
+                    revestimento.largura,
+                    revestimento.comprimento,
+                    revestimento.espessura,
+                ));
+            }
 
             let rejunte_total_arredondado = rejunte_total.ceil();
 
+            // Cores na ordem em que aparecem nos ambientes
+            let mut cores: Vec<(String, Decimal)> = Vec::new();
+            let mut por_ambiente = String::from("Rejunte por Ambiente:\n");
+            for (index, (ambiente, kg)) in self.ambientes.iter().zip(&rejunte_ambientes).enumerate() {
+                let cor = match ambiente.cor_rejunte.trim() {
+                    "" => "Cor não informada".to_string(),
+                    cor => cor.to_string(),
+                };
+                por_ambiente.push_str(&format!("Ambiente {} ({}): {:.2} kg\n", index + 1, cor, kg));
+                match cores.iter_mut().find(|(c, _)| *c == cor) {
+                    Some((_, total)) => *total += kg,
+                    None => cores.push((cor, *kg)),
+                }
+            }
+
+            let mut por_cor = String::from("Rejunte por Cor:\n");
+            let mut custo_total = Decimal::ZERO;
+            for (cor, kg) in &cores {
+                let combinacao = combinar_embalagens(*kg, &embalagens);
+                let descricao: Vec<String> = embalagens
+                    .iter()
+                    .zip(&combinacao)
+                    .filter(|(_, n)| **n > 0)
+                    .map(|((tamanho, _), n)| format!("{} x {} kg", n, tamanho))
+                    .collect();
+                let custo: Decimal = embalagens
+                    .iter()
+                    .zip(&combinacao)
+                    .map(|((_, preco), n)| preco * Decimal::from(*n))
+                    .sum();
+                custo_total += custo;
+
+                por_cor.push_str(&format!("{}: {:.2} kg -> {}", cor, kg, descricao.join(" + ")));
+                if com_precos {
+                    por_cor.push_str(&format!(" (R$ {:.2})", custo));
+                }
+                por_cor.push('\n');
+            }
+            if com_precos {
+                por_cor.push_str(&format!("Custo Total do Rejunte: R$ {:.2}\n", custo_total));
+            }
+
             self.resultado.push_str(&format!(
-                "Cálculo de Rejunte:\nÁrea Total: {:.2} m²\nRevestimento: {:.0}mm x {:.0}mm x {:.0}mm\n\
-                Espaçamento das Juntas: {:.2} mm\nCoeficiente de Rejuntamento: {:.2}\nQuantidade de Rejunte Necessária: {:.0} kg\n\n",
+                "Cálculo de Rejunte:\nÁrea Total: {:.2} m²\n{}\
+                {}Comprimento de Juntas: {:.2} m\n\
+                Espaçamento das Juntas: {:.2} mm\nCoeficiente de Rejuntamento: {:.2}\nQuantidade de Rejunte Necessária: {:.0} kg\n\n\
+                {}\n{}\n",
                 area_total,
-                tile_width,
-                tile_length,
-                tile_thickness,
+                revestimentos_texto,
+                if modo_parede {
+                    String::new()
+                } else {
+                    format!("Padrão de Assentamento: {}\n", self.padrao_assentamento.nome())
+                },
+                comprimento_juntas,
                 joint_spacing,
                 rejunte_coeficiente,
                 rejunte_total_arredondado,
+                por_ambiente,
+                por_cor,
             ));
         }
     }
-}
 
-fn parse_decimal(s: &str) -> Result<Decimal, rust_decimal::Error> {
-    let normalized = s.replace(',', ".");
-    Decimal::from_str(&normalized)
-}
+    // Paginação e rodapé só se aplicam ao piso
+    fn usa_paginacao(&self) -> bool {
+        self.calcular_piso && self.simular_paginacao && self.modo == ModoRevestimento::Piso
+    }
+
+    // Placas, argamassa, rejunte e espaçadores não se aplicam ao piso laminado
+    fn usa_piso(&self) -> bool {
+        self.calcular_piso && self.modo != ModoRevestimento::Laminado
+    }
+
+    fn usa_argamassa(&self) -> bool {
+        self.calcular_argamassa && self.modo != ModoRevestimento::Laminado
+    }
+
+    fn usa_rejunte(&self) -> bool {
+        self.calcular_rejunte && self.modo != ModoRevestimento::Laminado
+    }
+
+    fn usa_espacadores(&self) -> bool {
+        self.calcular_espacadores && self.modo != ModoRevestimento::Laminado
+    }
+
+    fn usa_rodape(&self) -> bool {
+        self.calcular_rodape && self.modo != ModoRevestimento::Parede
+    }
+
+    // No piso laminado o rodapé é sempre de régua pronta
+    fn tipo_rodape_aplicado(&self) -> TipoRodape {
+        match self.modo {
+            ModoRevestimento::Laminado => TipoRodape::Mdf,
+            _ => self.tipo_rodape,
+        }
+    }
+
+    fn usa_contrapiso(&self) -> bool {
+        self.calcular_contrapiso && self.modo != ModoRevestimento::Parede
+    }
+
+    fn usa_impermeabilizacao(&self) -> bool {
+        self.calcular_impermeabilizacao && self.modo == ModoRevestimento::Piso
+    }
+
+    // Área bruta das paredes e área das aberturas (m²)
+    fn area_paredes(paredes: &[Parede]) -> Result<(Decimal, Decimal), String> {
+        let mut area_bruta = Decimal::ZERO;
+        let mut area_aberturas = Decimal::ZERO;
+
+        for (indice, parede) in paredes.iter().enumerate() {
+            let comprimento = match parse_decimal(&parede.comprimento) {
+                Ok(v) if v >= Decimal::ZERO => v,
+                _ => return Err(format!("comprimento da parede {} inválido", indice + 1)),
+            };
+            let altura = match parse_decimal(&parede.altura) {
+                Ok(v) if v >= Decimal::ZERO => v,
+                _ => return Err(format!("altura da parede {} inválida", indice + 1)),
+            };
+            let aberturas = match area_aberturas_parede(&parede.aberturas) {
+                Some(v) => v,
+                None => {
+                    return Err(format!(
+                        "aberturas da parede {} inválidas (use largura x altura)",
+                        indice + 1
+                    ))
+                }
+            };
+
+            area_bruta += comprimento * altura;
+            area_aberturas += aberturas.min(comprimento * altura);
+        }
+
+        Ok((area_bruta, area_aberturas))
+    }
+
+    // Revestimento dos campos gerais; só valida o que as opções marcadas usam
+    fn revestimento_geral(&self, simular_paginacao: bool) -> Result<RevestimentoPiso, String> {
+        let mut revestimento = RevestimentoPiso {
+            nome: "Dados Gerais".to_string(),
+            largura: Decimal::ZERO,
+            comprimento: Decimal::ZERO,
+            espessura: Decimal::ZERO,
+            area_caixa: Decimal::ZERO,
+            pecas_caixa: 0,
+            peso_caixa: None,
+            preco_caixa: None,
+            porcelanato: self.porcelanato,
+        };
+
+        if self.usa_piso() && !simular_paginacao {
+            revestimento.area_caixa = match parse_decimal(&self.area_caixa) {
+                Ok(v) if v > Decimal::ZERO => v,
+                _ => return Err("Área da caixa inválida".to_string()),
+            };
+        }
+
+        let rodape_ceramico =
+            self.usa_rodape() && self.tipo_rodape_aplicado() == TipoRodape::Ceramico;
+
+        // No rodapé as peças por caixa são opcionais (sem elas só as placas são contadas)
+        if simular_paginacao || (rodape_ceramico && !self.pecas_caixa.trim().is_empty()) {
+            revestimento.pecas_caixa = match self.pecas_caixa.trim().parse::<u32>() {
+                Ok(v) if v > 0 => v,
+                _ => return Err("Peças por caixa inválido".to_string()),
+            };
+        }
+
+        // Para a argamassa as medidas são opcionais: sem elas a recomendação fica só pelo local
+        let medidas_em_branco =
+            self.tile_width.trim().is_empty() && self.tile_length.trim().is_empty();
+        if simular_paginacao
+            || self.usa_rejunte()
+            || (self.usa_argamassa() && !medidas_em_branco)
+            || self.usa_espacadores()
+            || rodape_ceramico
+        {
+            revestimento.largura = match parse_decimal(&self.tile_width) {
+                Ok(v) if v > Decimal::ZERO => v,
+                _ => return Err("Largura do revestimento inválida".to_string()),
+            };
+            revestimento.comprimento = match parse_decimal(&self.tile_length) {
+                Ok(v) if v > Decimal::ZERO => v,
+                _ => return Err("Comprimento do revestimento inválida".to_string()),
+            };
+        }
+
+        if self.usa_rejunte() {
+            revestimento.espessura = match parse_decimal(&self.tile_thickness) {
+                Ok(v) => v,
+                Err(_) => return Err("Espessura do revestimento inválida".to_string()),
+            };
+        }
+
+        Ok(revestimento)
+    }
+
+    // Na paginação os cortes já estão contados e fica só a quebra; peças grandes
+    // (lado a partir de 60 cm) quebram e perdem mais nos cortes
+    fn sugerir_perda(&mut self) {
+        let maior_lado = [&self.tile_width, &self.tile_length]
+            .iter()
+            .filter_map(|v| parse_decimal(v).ok())
+            .max()
+            .unwrap_or_default();
+        let adicional = if maior_lado >= dec!(600) { dec!(3) } else { Decimal::ZERO };
+
+        let perda = if self.usa_paginacao() {
+            dec!(2) + adicional / dec!(3)
+        } else {
+            self.padrao_assentamento.perda_sugerida() + adicional
+        };
+        self.percentual_perda = perda.normalize().to_string();
+    }
+
+    fn adicionar_produto(&mut self) -> bool {
+        match self.novo_produto.revestimento() {
+            Ok(_) => {
+                self.mensagem_catalogo = format!("Produto {} adicionado", self.novo_produto.nome);
+                self.produtos.push(std::mem::take(&mut self.novo_produto));
+                true
+            }
+            Err(erro) => {
+                self.mensagem_catalogo = erro;
+                false
+            }
+        }
+    }
+
+    fn remover_produto(&mut self, indice: usize) {
+        self.produtos.remove(indice);
+        for ambiente in &mut self.ambientes {
+            ambiente.produto = match ambiente.produto {
+                Some(p) if p == indice => None,
+                Some(p) if p > indice => Some(p - 1),
+                outro => outro,
+            };
+        }
+    }
+
+    // Preenche os dados gerais com o produto escolhido
+    fn usar_produto(&mut self, indice: usize) {
+        let produto = &self.produtos[indice];
+        self.tile_width = produto.largura.clone();
+        self.tile_length = produto.comprimento.clone();
+        self.tile_thickness = produto.espessura.clone();
+        self.area_caixa = produto.area_caixa.clone();
+        self.pecas_caixa = produto.pecas_caixa.clone();
+        self.porcelanato = produto.porcelanato;
+    }
+
+    // Fileiras ao longo do comprimento do ambiente (mm). O corte do início de uma régua deixa
+    // uma sobra que serve para terminar outra fileira, e o corte do final deixa uma que serve
+    // para começar; a fileira começa no maior início que respeita o deslocamento, e nenhuma
+    // peça fica menor que o deslocamento. Última fileira estreita divide a largura com a primeira.
+    fn paginar_laminado(
+        largura: Decimal,
+        comprimento: Decimal,
+        regua_comprimento: Decimal,
+        regua_largura: Decimal,
+        deslocamento: Decimal,
+    ) -> PaginacaoLaminado {
+        let fileiras = (largura / regua_largura).ceil().max(Decimal::ONE);
+        let mut largura_borda = largura - (fileiras - Decimal::ONE) * regua_largura;
+        let bordas_equilibradas = fileiras > Decimal::ONE && largura_borda < regua_largura / dec!(3);
+        if bordas_equilibradas {
+            largura_borda = (largura_borda + regua_largura) / dec!(2);
+        }
+
+        let final_fileira = |inicio: Decimal| (comprimento - inicio) % regua_comprimento;
+        let valido = |inicio: Decimal, anterior: Option<Decimal>| {
+            let fim = final_fileira(inicio);
+            inicio >= deslocamento
+                && (fim.is_zero() || fim >= deslocamento)
+                && match anterior {
+                    Some(a) => (inicio - a).abs() >= deslocamento,
+                    None => true,
+                }
+        };
+        // Menor sobra que ainda dá a peça pedida; devolve o que resta dela
+        let tirar_sobra = |sobras: &mut Vec<Decimal>, tamanho: Decimal| {
+            let indice = (0..sobras.len())
+                .filter(|i| sobras[*i] >= tamanho)
+                .min_by_key(|i| sobras[*i])?;
+            Some(sobras.swap_remove(indice) - tamanho)
+        };
+
+        let mut paginacao = PaginacaoLaminado {
+            fileiras: fileiras.to_u32().unwrap_or(0),
+            largura_borda,
+            bordas_equilibradas,
+            reguas: 0,
+            cortes_inicio: 0,
+            cortes_final: 0,
+        };
+        let mut sobras_inicio: Vec<Decimal> = Vec::new();
+        let mut sobras_final: Vec<Decimal> = Vec::new();
+        let mut inicio_anterior = None;
+
+        for _ in 0..paginacao.fileiras {
+            // Ambiente mais curto que a régua: uma peça por fileira
+            if comprimento <= regua_comprimento {
+                if comprimento < regua_comprimento {
+                    paginacao.cortes_final += 1;
+                }
+                let resto = match tirar_sobra(&mut sobras_inicio, comprimento) {
+                    Some(resto) => resto,
+                    None => {
+                        paginacao.reguas += 1;
+                        regua_comprimento - comprimento
+                    }
+                };
+                if resto >= comprimento {
+                    sobras_inicio.push(resto);
+                }
+                continue;
+            }
+
+            let reaproveitada = (0..sobras_inicio.len())
+                .filter(|i| valido(sobras_inicio[*i], inicio_anterior))
+                .max_by_key(|i| sobras_inicio[*i]);
+            let inicio = match reaproveitada {
+                Some(indice) => sobras_inicio.swap_remove(indice),
+                None => {
+                    let mut inicio = regua_comprimento;
+                    while !valido(inicio, inicio_anterior)
+                        && inicio - PASSO_CORTE_LAMINADO >= deslocamento
+                    {
+                        inicio -= PASSO_CORTE_LAMINADO;
+                    }
+                    if !valido(inicio, inicio_anterior) {
+                        inicio = regua_comprimento;
+                    }
+                    paginacao.reguas += 1;
+                    if inicio < regua_comprimento {
+                        sobras_final.push(regua_comprimento - inicio);
+                    }
+                    inicio
+                }
+            };
+            if inicio < regua_comprimento {
+                paginacao.cortes_inicio += 1;
+            }
+
+            let inteiras = ((comprimento - inicio) / regua_comprimento).floor();
+            paginacao.reguas += inteiras.to_u32().unwrap_or(0);
+
+            let fim = final_fileira(inicio);
+            if fim > Decimal::ZERO {
+                paginacao.cortes_final += 1;
+                // O resto da régua ou da sobra usada no final serve para começar outra fileira
+                let resto = match tirar_sobra(&mut sobras_final, fim) {
+                    Some(resto) => resto,
+                    None => {
+                        paginacao.reguas += 1;
+                        regua_comprimento - fim
+                    }
+                };
+                if resto >= deslocamento {
+                    sobras_inicio.push(resto);
+                }
+            }
+
+            sobras_inicio.retain(|s| *s >= deslocamento);
+            sobras_final.retain(|s| *s >= deslocamento);
+            inicio_anterior = Some(inicio);
+        }
+
+        paginacao
+    }
+
+    // Distribui as peças a partir do início escolhido conforme o padrão; o comprimento da
+    // peça segue o comprimento do ambiente. Cortes são tirados primeiro das sobras já abertas.
+    fn paginar(
+        largura: Decimal,
+        comprimento: Decimal,
+        peca_largura: Decimal,
+        peca_comprimento: Decimal,
+        junta: Decimal,
+        padrao: PadraoAssentamento,
+        inicio: InicioPaginacao,
+    ) -> PaginacaoPiso {
+        let mut inteiras = 0;
+        let mut cortes = Vec::new();
+        let mut meio_perimetro_pecas = Decimal::ZERO;
+
+        for recorte in Self::recortes_pecas(
+            largura,
+            comprimento,
+            peca_largura,
+            peca_comprimento,
+            junta,
+            padrao,
+            inicio,
+        ) {
+            meio_perimetro_pecas += perimetro_poligono(&recorte.visivel) / dec!(2);
+
+            if recorte.inteira {
+                inteiras += 1;
+            } else {
+                cortes.push(CortePiso {
+                    comprimento: recorte.comprimento,
+                    largura: recorte.largura,
+                    triangular: area_poligono(&recorte.visivel) * dec!(2)
+                        <= recorte.comprimento * recorte.largura * dec!(1.01),
+                });
+            }
+        }
+
+        let cortadas = cortes.len() as u32;
+
+        // Maiores cortes primeiro, cada um na menor sobra que o comporta (sem girar a peça)
+        cortes.sort_by_key(|c| std::cmp::Reverse(c.comprimento * c.largura));
+
+        let mut sobras: Vec<(Decimal, Decimal)> = Vec::new();
+        let mut sobras_triangulares: Vec<(Decimal, Decimal)> = Vec::new();
+        let mut pecas_para_cortes = 0;
+
+        for corte in cortes {
+            if corte.triangular {
+                let indice = sobras_triangulares
+                    .iter()
+                    .position(|s| s.0 >= corte.comprimento && s.1 >= corte.largura);
+                if let Some(i) = indice {
+                    sobras_triangulares.swap_remove(i);
+                    continue;
+                }
+            }
+
+            let indice = sobras
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| s.0 >= corte.comprimento && s.1 >= corte.largura)
+                .min_by_key(|(_, s)| s.0 * s.1)
+                .map(|(i, _)| i);
+
+            let (comprimento_origem, largura_origem) = match indice {
+                Some(i) => sobras.swap_remove(i),
+                None => {
+                    pecas_para_cortes += 1;
+                    (peca_comprimento, peca_largura)
+                }
+            };
+
+            if corte.triangular {
+                sobras_triangulares.push((corte.comprimento, corte.largura));
+            }
+
+            // Corte em guilhotina: retalho ao lado do corte e retalho na faixa restante
+            if comprimento_origem - corte.comprimento >= TAMANHO_MINIMO_SOBRA_PISO {
+                sobras.push((comprimento_origem - corte.comprimento, largura_origem));
+            }
+            if largura_origem - corte.largura >= TAMANHO_MINIMO_SOBRA_PISO {
+                sobras.push((corte.comprimento, largura_origem - corte.largura));
+            }
+        }
+
+        sobras.extend(sobras_triangulares);
+
+        // Cada peça contribui com metade do seu contorno; junto às paredes não há junta
+        let comprimento_juntas =
+            (meio_perimetro_pecas - comprimento - largura).max(Decimal::ZERO);
+
+        PaginacaoPiso {
+            inteiras,
+            cortadas,
+            pecas_para_cortes,
+            sobras,
+            comprimento_juntas,
+        }
+    }
+
+    // Trecho visível de cada peça do padrão, com a classificação em inteira ou cortada
+    fn recortes_pecas(
+        largura: Decimal,
+        comprimento: Decimal,
+        peca_largura: Decimal,
+        peca_comprimento: Decimal,
+        junta: Decimal,
+        padrao: PadraoAssentamento,
+        inicio: InicioPaginacao,
+    ) -> Vec<RecortePeca> {
+        let origem = Self::origem_padrao(largura, comprimento, peca_largura, peca_comprimento, padrao, inicio);
+        let mut recortes = Vec::new();
+
+        for peca in Self::pecas_padrao(largura, comprimento, peca_largura, peca_comprimento, junta, padrao, origem) {
+            let (ox, oy) = peca.origem;
+            let (cx, cy) = peca.eixo_comprimento;
+            let (lx, ly) = peca.eixo_largura;
+            let cantos = vec![
+                (ox, oy),
+                (ox + cx * peca_comprimento, oy + cy * peca_comprimento),
+                (
+                    ox + cx * peca_comprimento + lx * peca_largura,
+                    oy + cy * peca_comprimento + ly * peca_largura,
+                ),
+                (ox + lx * peca_largura, oy + ly * peca_largura),
+            ];
+
+            let visivel = recortar_poligono(cantos, comprimento, largura);
+            if visivel.len() < 3 {
+                continue;
+            }
+
+            // Coordenadas do trecho visível no sistema da própria peça
+            let locais: Vec<(Decimal, Decimal)> = visivel
+                .iter()
+                .map(|(x, y)| ((x - ox) * cx + (y - oy) * cy, (x - ox) * lx + (y - oy) * ly))
+                .collect();
+            let comprimento_corte = locais.iter().map(|p| p.0).max().unwrap_or_default()
+                - locais.iter().map(|p| p.0).min().unwrap_or_default();
+            let largura_corte = locais.iter().map(|p| p.1).max().unwrap_or_default()
+                - locais.iter().map(|p| p.1).min().unwrap_or_default();
+
+            if comprimento_corte < TAMANHO_MINIMO_CORTE_PISO || largura_corte < TAMANHO_MINIMO_CORTE_PISO {
+                continue;
+            }
+
+            // Tolerância para o arredondamento das coordenadas na diagonal
+            let tolerancia = dec!(0.000001);
+            recortes.push(RecortePeca {
+                visivel,
+                comprimento: comprimento_corte,
+                largura: largura_corte,
+                inteira: comprimento_corte >= peca_comprimento - tolerancia
+                    && largura_corte >= peca_largura - tolerancia,
+            });
+        }
+
+        recortes
+    }
+
+    // Canto de origem da peça de referência do padrão: no canto do ambiente ou com a
+    // peça centralizada
+    fn origem_padrao(
+        largura: Decimal,
+        comprimento: Decimal,
+        peca_largura: Decimal,
+        peca_comprimento: Decimal,
+        padrao: PadraoAssentamento,
+        inicio: InicioPaginacao,
+    ) -> (Decimal, Decimal) {
+        match inicio {
+            InicioPaginacao::Canto => (Decimal::ZERO, Decimal::ZERO),
+            InicioPaginacao::Centro => {
+                let (eixo_c, eixo_l) = match padrao {
+                    PadraoAssentamento::Diagonal => ((SENO_45, SENO_45), (-SENO_45, SENO_45)),
+                    _ => ((Decimal::ONE, Decimal::ZERO), (Decimal::ZERO, Decimal::ONE)),
+                };
+                (
+                    (comprimento - eixo_c.0 * peca_comprimento - eixo_l.0 * peca_largura) / dec!(2),
+                    (largura - eixo_c.1 * peca_comprimento - eixo_l.1 * peca_largura) / dec!(2),
+                )
+            }
+        }
+    }
+
+    // Peças do padrão ancorado em `origem` que cobrem o ambiente (as que caem fora são
+    // descartadas no recorte)
+    fn pecas_padrao(
+        largura: Decimal,
+        comprimento: Decimal,
+        peca_largura: Decimal,
+        peca_comprimento: Decimal,
+        junta: Decimal,
+        padrao: PadraoAssentamento,
+        origem: (Decimal, Decimal),
+    ) -> Vec<PecaPiso> {
+        let modulo_comprimento = peca_comprimento + junta;
+        let modulo_largura = peca_largura + junta;
+        let horizontal = (Decimal::ONE, Decimal::ZERO);
+        let vertical = (Decimal::ZERO, Decimal::ONE);
+        let (ox, oy) = origem;
+        let cantos = [
+            (-ox, -oy),
+            (comprimento - ox, -oy),
+            (-ox, largura - oy),
+            (comprimento - ox, largura - oy),
+        ];
+        // Faixa de índices da grade que cobre os cantos do ambiente, com uma peça de folga
+        let faixa = |coordenada: &dyn Fn((Decimal, Decimal)) -> Decimal, modulo: Decimal| {
+            let valores = cantos.map(|c| coordenada(c) / modulo);
+            (
+                valores.iter().min().copied().unwrap_or_default().floor() - dec!(2),
+                valores.iter().max().copied().unwrap_or_default().ceil() + Decimal::ONE,
+            )
+        };
+        let mut pecas = Vec::new();
+
+        match padrao {
+            PadraoAssentamento::Reto
+            | PadraoAssentamento::AmarracaoMeia
+            | PadraoAssentamento::AmarracaoTerco => {
+                let deslocamento = match padrao {
+                    PadraoAssentamento::AmarracaoMeia => dec!(0.5),
+                    PadraoAssentamento::AmarracaoTerco => Decimal::ONE / dec!(3),
+                    _ => Decimal::ZERO,
+                };
+
+                let mut fileira = (-oy / modulo_largura).ceil();
+                let mut y = oy + fileira * modulo_largura;
+                if y > Decimal::ZERO {
+                    fileira -= Decimal::ONE;
+                    y -= modulo_largura;
+                }
+                while y < largura {
+                    let avanco = fileira * deslocamento;
+                    let inicio_fileira = ox - (avanco - avanco.floor()) * modulo_comprimento;
+                    let mut x = inicio_fileira - (inicio_fileira / modulo_comprimento).ceil() * modulo_comprimento;
+                    while x < comprimento {
+                        pecas.push(PecaPiso {
+                            origem: (x, y),
+                            eixo_comprimento: horizontal,
+                            eixo_largura: vertical,
+                        });
+                        x += modulo_comprimento;
+                    }
+                    y += modulo_largura;
+                    fileira += Decimal::ONE;
+                }
+            }
+            PadraoAssentamento::Diagonal => {
+                // Grade girada 45°: u ao longo do comprimento da peça, v ao longo da largura
+                let eixo_u = (SENO_45, SENO_45);
+                let eixo_v = (-SENO_45, SENO_45);
+                let (i_min, i_max) = faixa(&|(x, y)| (x + y) * SENO_45, modulo_comprimento);
+                let (j_min, j_max) = faixa(&|(x, y)| (y - x) * SENO_45, modulo_largura);
+
+                let mut i = i_min;
+                while i <= i_max {
+                    let mut j = j_min;
+                    while j <= j_max {
+                        let u = i * modulo_comprimento;
+                        let v = j * modulo_largura;
+                        pecas.push(PecaPiso {
+                            origem: (
+                                ox + u * eixo_u.0 + v * eixo_v.0,
+                                oy + u * eixo_u.1 + v * eixo_v.1,
+                            ),
+                            eixo_comprimento: eixo_u,
+                            eixo_largura: eixo_v,
+                        });
+                        j += Decimal::ONE;
+                    }
+                    i += Decimal::ONE;
+                }
+            }
+            PadraoAssentamento::EspinhaDePeixe => {
+                // Par de peças (uma deitada, uma em pé) repetido pelos vetores
+                // (L, L) e (C, -C), com L e C já somados à junta
+                let (m_min, m_max) = faixa(&|(x, y)| (x + y) / dec!(2), modulo_largura);
+                let (n_min, n_max) = faixa(&|(x, y)| (x - y) / dec!(2), modulo_comprimento);
+
+                let mut m = m_min - Decimal::ONE;
+                while m <= m_max + Decimal::ONE {
+                    let mut n = n_min - Decimal::ONE;
+                    while n <= n_max + Decimal::ONE {
+                        let x = ox + m * modulo_largura + n * modulo_comprimento;
+                        let y = oy + m * modulo_largura - n * modulo_comprimento;
+                        let dentro = x < comprimento
+                            && y - modulo_comprimento < largura
+                            && x + modulo_comprimento + modulo_largura > Decimal::ZERO
+                            && y + modulo_largura > Decimal::ZERO;
+                        if dentro {
+                            pecas.push(PecaPiso {
+                                origem: (x, y),
+                                eixo_comprimento: horizontal,
+                                eixo_largura: vertical,
+                            });
+                            pecas.push(PecaPiso {
+                                origem: (
+                                    x + modulo_comprimento + peca_largura,
+                                    y + modulo_largura - modulo_comprimento,
+                                ),
+                                eixo_comprimento: vertical,
+                                eixo_largura: (-Decimal::ONE, Decimal::ZERO),
+                            });
+                        }
+                        n += Decimal::ONE;
+                    }
+                    m += Decimal::ONE;
+                }
+            }
+        }
+
+        pecas
+    }
+
+    const COR_PECA: [u8; 3] = [225, 215, 190];
+    const COR_PECA_CORTADA: [u8; 3] = [240, 150, 60];
+    const COR_JUNTA: [u8; 3] = [90, 90, 90];
+    const COR_CONTORNO: [u8; 3] = [150, 150, 150];
+    const COR_INICIO: [u8; 3] = [220, 40, 40];
+
+    fn chave_desenho(&self, ambiente: &Ambiente) -> ChaveDesenho {
+        let (peca_largura, peca_comprimento) = match ambiente.produto.and_then(|p| self.produtos.get(p)) {
+            Some(produto) => (produto.largura.clone(), produto.comprimento.clone()),
+            None => (self.tile_width.clone(), self.tile_length.clone()),
+        };
+        ChaveDesenho {
+            largura: ambiente.largura.clone(),
+            comprimento: ambiente.comprimento.clone(),
+            peca_largura,
+            peca_comprimento,
+            junta: self.joint_spacing.clone(),
+            padrao: self.padrao_assentamento,
+            inicio: self.inicio_paginacao,
+        }
+    }
+
+    // Refaz apenas os desenhos dos ambientes cujos dados mudaram desde o último quadro
+    fn atualizar_desenhos_paginacao(&mut self) {
+        self.desenhos_paginacao.truncate(self.ambientes.len());
+        for index in 0..self.ambientes.len() {
+            let chave = self.chave_desenho(&self.ambientes[index]);
+            if self.desenhos_paginacao.get(index).is_some_and(|(anterior, _)| *anterior == chave) {
+                continue;
+            }
+            let desenho = self.desenho_paginacao(&self.ambientes[index]);
+            if index < self.desenhos_paginacao.len() {
+                self.desenhos_paginacao[index] = (chave, desenho);
+            } else {
+                self.desenhos_paginacao.push((chave, desenho));
+            }
+        }
+    }
+
+    // Desenho da paginação de um ambiente com os dados atuais do formulário
+    fn desenho_paginacao(&self, ambiente: &Ambiente) -> Result<Vec<FormaDesenho>, String> {
+        let positivo = |valor: &str| parse_decimal(valor).ok().filter(|v| *v > Decimal::ZERO);
+        let (Some(largura), Some(comprimento)) =
+            (positivo(&ambiente.largura), positivo(&ambiente.comprimento))
+        else {
+            return Err("Informe as medidas do ambiente".to_string());
+        };
+
+        let (peca_largura, peca_comprimento) =
+            match ambiente.produto.and_then(|p| self.produtos.get(p)) {
+                Some(produto) => (positivo(&produto.largura), positivo(&produto.comprimento)),
+                None => (positivo(&self.tile_width), positivo(&self.tile_length)),
+            };
+        let (Some(peca_largura), Some(peca_comprimento)) = (peca_largura, peca_comprimento) else {
+            return Err("Informe as medidas do revestimento".to_string());
+        };
+        let junta = match parse_decimal(&self.joint_spacing) {
+            Ok(v) if v >= Decimal::ZERO => v,
+            _ => return Err("Espaçamento das juntas inválido".to_string()),
+        };
+
+        let (peca_largura, peca_comprimento, junta) =
+            (peca_largura / dec!(1000), peca_comprimento / dec!(1000), junta / dec!(1000));
+        if largura * comprimento / (peca_largura * peca_comprimento) > LIMITE_PECAS_DESENHO {
+            return Err("Peças demais para desenhar neste ambiente".to_string());
+        }
+
+        let metros = |valor: Decimal| valor.to_f32().unwrap_or(0.0);
+        let recortes = Self::recortes_pecas(
+            largura,
+            comprimento,
+            peca_largura,
+            peca_comprimento,
+            junta,
+            self.padrao_assentamento,
+            self.inicio_paginacao,
+        );
+
+        // Fundo na cor da junta; cada peça desenhada por cima com o contorno da junta
+        let mut formas = vec![FormaDesenho::Retangulo {
+            x: 0.0,
+            y: 0.0,
+            largura: metros(comprimento),
+            altura: metros(largura),
+            cor: Self::COR_JUNTA,
+            preenchido: true,
+        }];
+        let mut cortadas = 0;
+        for recorte in &recortes {
+            let pontos: Vec<(f32, f32)> =
+                recorte.visivel.iter().map(|(x, y)| (metros(*x), metros(*y))).collect();
+            if !recorte.inteira {
+                cortadas += 1;
+            }
+            formas.push(FormaDesenho::Poligono {
+                pontos: pontos.clone(),
+                cor: if recorte.inteira { Self::COR_PECA } else { Self::COR_PECA_CORTADA },
+                preenchido: true,
+            });
+            formas.push(FormaDesenho::Poligono {
+                pontos,
+                cor: Self::COR_JUNTA,
+                preenchido: false,
+            });
+        }
+
+        formas.push(FormaDesenho::Retangulo {
+            x: 0.0,
+            y: 0.0,
+            largura: metros(comprimento),
+            altura: metros(largura),
+            cor: Self::COR_CONTORNO,
+            preenchido: false,
+        });
+
+        // Marca do ponto de início
+        let (x, y) = match self.inicio_paginacao {
+            InicioPaginacao::Canto => (0.0, 0.0),
+            InicioPaginacao::Centro => (metros(comprimento) / 2.0, metros(largura) / 2.0),
+        };
+        let marca = metros(peca_comprimento.min(peca_largura)) / 2.0;
+        formas.push(FormaDesenho::Linha {
+            x1: x - marca,
+            y1: y,
+            x2: x + marca,
+            y2: y,
+            cor: Self::COR_INICIO,
+            espessura: 2.0,
+        });
+        formas.push(FormaDesenho::Linha {
+            x1: x,
+            y1: y - marca,
+            x2: x,
+            y2: y + marca,
+            cor: Self::COR_INICIO,
+            espessura: 2.0,
+        });
+
+        formas.push(FormaDesenho::Texto {
+            x: metros(comprimento) / 2.0,
+            y: metros(largura) + 0.2,
+            texto: format!(
+                "{:.2} x {:.2} m | {} inteiras, {} cortadas (em destaque) | início no {}",
+                comprimento,
+                largura,
+                recortes.len() - cortadas,
+                cortadas,
+                self.inicio_paginacao.nome().to_lowercase()
+            ),
+        });
+
+        Ok(formas)
+    }
+}
+
+fn parse_decimal(s: &str) -> Result<Decimal, rust_decimal::Error> {
+    let normalized = s.replace(',', ".");
+    Decimal::from_str(&normalized)
+}
+
+// Recorte de um polígono convexo pelo retângulo [0, comprimento] x [0, largura]
+fn recortar_poligono(
+    pontos: Vec<(Decimal, Decimal)>,
+    comprimento: Decimal,
+    largura: Decimal,
+) -> Vec<(Decimal, Decimal)> {
+    let mut poligono = pontos;
+
+    // (limite no eixo x, valor do limite, mantém o lado acima do limite)
+    for (eixo_x, limite, acima) in [
+        (true, Decimal::ZERO, true),
+        (true, comprimento, false),
+        (false, Decimal::ZERO, true),
+        (false, largura, false),
+    ] {
+        let coordenada = |p: &(Decimal, Decimal)| if eixo_x { p.0 } else { p.1 };
+        let dentro = |p: &(Decimal, Decimal)| {
+            if acima {
+                coordenada(p) >= limite
+            } else {
+                coordenada(p) <= limite
+            }
+        };
+        let intersecao = |a: &(Decimal, Decimal), b: &(Decimal, Decimal)| {
+            let t = (limite - coordenada(a)) / (coordenada(b) - coordenada(a));
+            (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+        };
+
+        let mut recortado = Vec::new();
+        for i in 0..poligono.len() {
+            let atual = poligono[i];
+            let anterior = poligono[(i + poligono.len() - 1) % poligono.len()];
+            if dentro(&atual) {
+                if !dentro(&anterior) {
+                    recortado.push(intersecao(&anterior, &atual));
+                }
+                recortado.push(atual);
+            } else if dentro(&anterior) {
+                recortado.push(intersecao(&anterior, &atual));
+            }
+        }
+
+        poligono = recortado;
+        if poligono.is_empty() {
+            break;
+        }
+    }
+
+    poligono
+}
+
+fn area_poligono(pontos: &[(Decimal, Decimal)]) -> Decimal {
+    let mut soma = Decimal::ZERO;
+    for i in 0..pontos.len() {
+        let (x1, y1) = pontos[i];
+        let (x2, y2) = pontos[(i + 1) % pontos.len()];
+        soma += x1 * y2 - x2 * y1;
+    }
+    soma.abs() / dec!(2)
+}
+
+fn perimetro_poligono(pontos: &[(Decimal, Decimal)]) -> Decimal {
+    let mut soma = Decimal::ZERO;
+    for i in 0..pontos.len() {
+        let (x1, y1) = pontos[i];
+        let (x2, y2) = pontos[(i + 1) % pontos.len()];
+        let (dx, dy) = ((x2 - x1).abs(), (y2 - y1).abs());
+        soma += if dx.is_zero() || dy.is_zero() {
+            dx + dy
+        } else {
+            let lado = (dx * dx + dy * dy).to_f64().unwrap_or(0.0).sqrt();
+            Decimal::from_f64(lado).unwrap_or_default()
+        };
+    }
+    soma
+}
+
+// Lista de valores positivos separados por ';' (a vírgula é reservada para o decimal)
+fn parse_lista_decimal(s: &str) -> Option<Vec<Decimal>> {
+    s.split(';')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| parse_decimal(item).ok().filter(|v| *v > Decimal::ZERO))
+        .collect()
+}
+
+// Quantidade de cada embalagem (tamanho, custo) que cobre a quantidade pedida com o
+// menor custo; no empate fica a combinação com menos embalagens e, persistindo, a que
+// rende mais. Mochila ilimitada sobre os totais múltiplos do MDC dos tamanhos, até a
+// quantidade pedida mais a maior embalagem.
+fn combinar_embalagens(quantidade: Decimal, embalagens: &[(Decimal, Decimal)]) -> Vec<u32> {
+    fn mdc(a: u64, b: u64) -> u64 {
+        if b == 0 {
+            a
+        } else {
+            mdc(b, a % b)
+        }
+    }
+
+    let nenhuma = vec![0; embalagens.len()];
+    let escala = embalagens.iter().map(|(tamanho, _)| tamanho.normalize().scale()).max().unwrap_or(0);
+    let fator = Decimal::from(10u64.pow(escala));
+    let tamanhos: Vec<u64> = embalagens
+        .iter()
+        .map(|(tamanho, _)| (tamanho * fator).to_u64().unwrap_or(0))
+        .collect();
+    if tamanhos.is_empty() || tamanhos.contains(&0) {
+        return nenhuma;
+    }
+
+    let passo = tamanhos.iter().fold(0, |a, b| mdc(a, *b));
+    let unidades: Vec<usize> = tamanhos.iter().map(|tamanho| (tamanho / passo) as usize).collect();
+    let alvo = match (quantidade.max(Decimal::ZERO) * fator / Decimal::from(passo)).ceil().to_usize() {
+        Some(v) => v,
+        None => return nenhuma,
+    };
+    let limite = alvo + unidades.iter().max().unwrap_or(&0);
+
+    // Para cada total: (custo, embalagens, última embalagem usada)
+    let mut melhor: Vec<Option<(Decimal, u32, usize)>> = vec![None; limite + 1];
+    melhor[0] = Some((Decimal::ZERO, 0, 0));
+    for total in 1..=limite {
+        for (indice, &unidade) in unidades.iter().enumerate() {
+            if unidade > total {
+                continue;
+            }
+            if let Some((custo, quantidade, _)) = melhor[total - unidade] {
+                let candidato = (custo + embalagens[indice].1, quantidade + 1, indice);
+                let melhorou = match melhor[total] {
+                    Some((melhor_custo, melhor_quantidade, _)) => {
+                        (candidato.0, candidato.1) < (melhor_custo, melhor_quantidade)
+                    }
+                    None => true,
+                };
+                if melhorou {
+                    melhor[total] = Some(candidato);
+                }
+            }
+        }
+    }
+
+    let fim = (alvo..=limite)
+        .rev()
+        .filter_map(|total| melhor[total].map(|(custo, quantidade, _)| (total, custo, quantidade)))
+        .min_by_key(|(_, custo, quantidade)| (*custo, *quantidade));
+
+    let mut combinacao = nenhuma;
+    if let Some((mut total, _, _)) = fim {
+        while total > 0 {
+            let Some((_, _, indice)) = melhor[total] else { break };
+            combinacao[indice] += 1;
+            total -= unidades[indice];
+        }
+    }
+    combinacao
+}
+
+// Soma das aberturas no formato "1.20x1.00; 0.80x2.10"; medidas precisam ser positivas
+fn area_aberturas_parede(s: &str) -> Option<Decimal> {
+    let positivo = |valor: &str| parse_decimal(valor.trim()).ok().filter(|v| *v > Decimal::ZERO);
+    let mut area = Decimal::ZERO;
+    for item in s.split(';').map(str::trim).filter(|item| !item.is_empty()) {
+        let (largura, altura) = item.split_once(['x', 'X', '*'])?;
+        area += positivo(largura)? * positivo(altura)?;
+    }
+    Some(area)
+}
 
 impl CalculadoraLaje {
+    // Só treliça e isopor têm vigas apoiadas nas paredes
+    fn possui_vigas(&self) -> bool {
+        self.ambientes
+            .iter()
+            .any(|ambiente| ambiente.tipo_laje.largura_step().is_some())
+    }
+
     fn calcular(&mut self) {
+        self.desenhos.clear();
+        self.mensagem_exportacao.clear();
+
         let mut resultado = String::new();
+        let mut desenhos = Vec::new();
         let mut total_area = Decimal::ZERO;
-        let mut total_elementos = 0u32; // Lajotas ou placas de isopor
+        let mut resumo_por_tipo: Vec<ResumoTipoLaje> = Vec::new();
 
         // HashMap para armazenar a contagem de vigas por comprimento
         let mut beams_per_length: HashMap<Decimal, u32> = HashMap::new();
 
+        let espessura_parede = match parse_decimal(&self.espessura_parede) {
+            Ok(v) if v >= Decimal::ZERO => v,
+            _ => {
+                self.resultado = "Espessura da parede inválida".to_string();
+                return;
+            }
+        };
+        let mut apoio = Decimal::ZERO;
+        if self.possui_vigas() {
+            apoio = match parse_decimal(&self.apoio_viga) {
+                Ok(v) if v >= Decimal::ZERO => v,
+                _ => {
+                    self.resultado = "Apoio da viga inválido".to_string();
+                    return;
+                }
+            };
+            if apoio > espessura_parede {
+                self.resultado = "Apoio da viga maior que a espessura da parede".to_string();
+                return;
+            }
+        }
+
+        // Parâmetros da laje maciça
+        let mut espessura_macica = Decimal::ZERO;
+        let mut reaproveitamento_forma = Decimal::ONE;
+        let mut taxa_aco_macica = Decimal::ZERO;
+        if self
+            .ambientes
+            .iter()
+            .any(|ambiente| ambiente.tipo_laje == TipoLaje::Macica)
+        {
+            espessura_macica = match parse_decimal(&self.espessura_macica) {
+                Ok(v) if v > Decimal::ZERO => v,
+                _ => {
+                    self.resultado = "Espessura da laje maciça inválida".to_string();
+                    return;
+                }
+            };
+            reaproveitamento_forma = match parse_decimal(&self.reaproveitamento_forma) {
+                Ok(v) if v >= Decimal::ONE => v,
+                _ => {
+                    self.resultado = "Número de usos da chapa inválido".to_string();
+                    return;
+                }
+            };
+            taxa_aco_macica = match parse_decimal(&self.taxa_aco_macica) {
+                Ok(v) if v >= Decimal::ZERO => v,
+                _ => {
+                    self.resultado = "Taxa de aço inválida".to_string();
+                    return;
+                }
+            };
+        }
+        let mut espessura_capa = Decimal::ZERO;
+        if self
+            .ambientes
+            .iter()
+            .any(|ambiente| ambiente.tipo_laje != TipoLaje::Macica)
+        {
+            espessura_capa = match parse_decimal(&self.espessura_capa) {
+                Ok(v) if v > Decimal::ZERO => v,
+                _ => {
+                    self.resultado = "Capa de concreto inválida".to_string();
+                    return;
+                }
+            };
+        }
+
+        // Parâmetros das cargas
+        let mut carga_revestimento = Decimal::ZERO;
+        let mut carga_acidental = Decimal::ZERO;
+        if self.calcular_cargas {
+            carga_revestimento = match parse_decimal(&self.carga_revestimento) {
+                Ok(v) if v >= Decimal::ZERO => v,
+                _ => {
+                    self.resultado = "Carga de revestimento inválida".to_string();
+                    return;
+                }
+            };
+            carga_acidental = match parse_decimal(&self.carga_acidental) {
+                Ok(v) if v >= Decimal::ZERO => v,
+                _ => {
+                    self.resultado = "Carga acidental inválida".to_string();
+                    return;
+                }
+            };
+        }
+        let mut total_carga = Decimal::ZERO;
+        let mut total_chapas_forma = 0u32;
+        let mut total_madeira_forma = Decimal::ZERO;
+        let mut total_aco_macica = Decimal::ZERO;
+
+        // Parâmetros da armadura de distribuição (tela ou barras)
+        let mut transpasse = Decimal::ZERO;
+        let mut diametro = Decimal::ZERO;
+        let mut espacamento = Decimal::ZERO;
+        if self.calcular_armadura {
+            match self.tipo_armadura {
+                TipoArmadura::TelaSoldada => {
+                    transpasse = match parse_decimal(&self.transpasse_tela) {
+                        Ok(v) if v >= Decimal::ZERO && v < self.painel_tela.dimensoes().0 => v,
+                        _ => {
+                            self.resultado = "Transpasse da tela inválido".to_string();
+                            return;
+                        }
+                    };
+                }
+                TipoArmadura::Barras => {
+                    diametro = match parse_decimal(&self.diametro_barra) {
+                        Ok(v) if v > Decimal::ZERO => v,
+                        _ => {
+                            self.resultado = "Diâmetro da barra inválido".to_string();
+                            return;
+                        }
+                    };
+                    espacamento = match parse_decimal(&self.espacamento_barra) {
+                        Ok(v) if v > Decimal::ZERO => v,
+                        _ => {
+                            self.resultado = "Espaçamento das barras inválido".to_string();
+                            return;
+                        }
+                    };
+                }
+            }
+        }
+        let mut total_paineis = 0u32;
+        let mut total_barras_metros = Decimal::ZERO;
+
+        // Parâmetros do escoramento
+        let mut espacamento_linhas = Decimal::ZERO;
+        let mut espacamento_escoras = Decimal::ZERO;
+        let mut locacao = None;
+        if self.calcular_escoramento {
+            espacamento_linhas = match parse_decimal(&self.espacamento_linhas_escora) {
+                Ok(v) if v > Decimal::ZERO => v,
+                _ => {
+                    self.resultado = "Distância entre linhas de escoramento inválida".to_string();
+                    return;
+                }
+            };
+            espacamento_escoras = match parse_decimal(&self.espacamento_escoras) {
+                Ok(v) if v > Decimal::ZERO => v,
+                _ => {
+                    self.resultado = "Distância entre escoras inválida".to_string();
+                    return;
+                }
+            };
+            if !self.valor_diaria_escora.is_empty() {
+                let diaria = match parse_decimal(&self.valor_diaria_escora) {
+                    Ok(v) if v >= Decimal::ZERO => v,
+                    _ => {
+                        self.resultado = "Valor da diária da escora inválido".to_string();
+                        return;
+                    }
+                };
+                let dias = match parse_decimal(&self.dias_locacao) {
+                    Ok(v) if v >= Decimal::ZERO => v,
+                    _ => {
+                        self.resultado = "Dias de locação inválidos".to_string();
+                        return;
+                    }
+                };
+                locacao = Some((diaria, dias));
+            }
+        }
+        // Parâmetros do plano de corte
+        let mut comprimento_estoque = Decimal::ZERO;
+        let mut sobras = Vec::new();
+        if self.calcular_plano_corte {
+            comprimento_estoque = match parse_decimal(&self.comprimento_estoque_viga) {
+                Ok(v) if v > Decimal::ZERO => v,
+                _ => {
+                    self.resultado = "Comprimento da barra de estoque inválido".to_string();
+                    return;
+                }
+            };
+            sobras = match parse_lista_decimal(&self.sobras_vigas) {
+                Some(v) => v,
+                None => {
+                    self.resultado = "Lista de sobras inválida".to_string();
+                    return;
+                }
+            };
+        }
+        let mut total_linhas_escora = 0u32;
+        let mut total_escoras = 0u32;
+        let mut total_guias_metros = Decimal::ZERO;
+
         for (i, ambiente) in self.ambientes.iter().enumerate() {
             let largura_original = match parse_decimal(&ambiente.largura) {
                 Ok(v) => v,
@@ -1251,95 +5226,603 @@ impl CalculadoraLaje {
                 }
             };
 
-            let (lado_instalacao, lado_perpendicular) = match ambiente.direcao {
-                DirecaoLaje::MaiorLado => {
-                    if largura_original >= comprimento_original {
-                        (largura_original, comprimento_original)
+            let (mut largura, mut comprimento) = (largura_original, comprimento_original);
+
+            // Medidas de eixo a eixo perdem meia parede de cada lado
+            if self.referencia_medida == ReferenciaMedida::EixoParede {
+                largura -= espessura_parede;
+                comprimento -= espessura_parede;
+            }
+            if largura <= Decimal::ZERO || comprimento <= Decimal::ZERO {
+                self.resultado = format!("Medidas menores que a parede no ambiente {}", i + 1);
+                return;
+            }
+
+            let largura_step = ambiente.tipo_laje.largura_step();
+
+            // Maciça e nervurada não têm vigas: sem comparação de direção nem treliça
+            let direcao_ambiente = if largura_step.is_none() {
+                DirecaoLaje::MenorLado
+            } else {
+                ambiente.direcao
+            };
+
+            let mut comparacao = String::new();
+            let layout = match direcao_ambiente {
+                DirecaoLaje::Automatico => {
+                    let opcoes = [DirecaoLaje::MenorLado, DirecaoLaje::MaiorLado].map(|direcao| {
+                        Self::layout(&largura, &comprimento, direcao, largura_step.as_ref(), &apoio)
+                    });
+                    let textos: Vec<String> = opcoes
+                        .iter()
+                        .map(|opcao| {
+                            format!(
+                                "{} = {} vigas de {:.2} m ({:.2} m)",
+                                opcao.direcao.nome(),
+                                opcao.num_vigas,
+                                opcao.comprimento_ajustado,
+                                opcao.metros_vigas()
+                            )
+                        })
+                        .collect();
+                    comparacao = format!("Comparação: {}\n", textos.join(" | "));
+
+                    let [menor, maior] = opcoes;
+                    Self::melhor_layout(menor, maior, self.uso_laje)
+                }
+                direcao => {
+                    Self::layout(&largura, &comprimento, direcao, largura_step.as_ref(), &apoio)
+                }
+            };
+            let LayoutLaje {
+                direcao,
+                lado_instalacao,
+                lado_perpendicular,
+                comprimento_ajustado,
+                largura_ajustada,
+                num_vigas,
+            } = layout;
+
+            let area_ambiente = lado_instalacao * lado_perpendicular;
+            total_area += area_ambiente;
+
+            // Acumular totais por tipo de laje
+            let indice_resumo = match resumo_por_tipo
+                .iter()
+                .position(|resumo| resumo.tipo == ambiente.tipo_laje)
+            {
+                Some(indice) => indice,
+                None => {
+                    resumo_por_tipo.push(ResumoTipoLaje {
+                        tipo: ambiente.tipo_laje,
+                        area: Decimal::ZERO,
+                        vigas: HashMap::new(),
+                        blocos: HashMap::new(),
+                        volume_concreto: Decimal::ZERO,
+                    });
+                    resumo_por_tipo.len() - 1
+                }
+            };
+            let resumo = &mut resumo_por_tipo[indice_resumo];
+            resumo.area += area_ambiente;
+
+            resultado.push_str(&format!(
+                "Ambiente {}:\nTipo de Laje: {}\n",
+                i + 1,
+                ambiente.tipo_laje.nome()
+            ));
+            if largura_step.is_some() {
+                resultado.push_str(&format!(
+                    "Direção de instalação: {}\n{}",
+                    if ambiente.direcao == DirecaoLaje::Automatico {
+                        format!("Automático ({})", direcao.nome())
                     } else {
-                        (comprimento_original, largura_original)
+                        direcao.nome().to_string()
+                    },
+                    comparacao,
+                ));
+            }
+
+            // Volume concretado na obra e peso das peças pré-moldadas (kN)
+            let (volume_ambiente, peso_pre_moldados) = match ambiente.tipo_laje {
+                TipoLaje::Macica => {
+                    let volume = area_ambiente * espessura_macica;
+                    let chapas = Self::calc_chapas_forma(&lado_instalacao, &lado_perpendicular);
+                    let madeira = Self::calc_madeira_forma(&lado_instalacao, &lado_perpendicular);
+                    let aco = volume * taxa_aco_macica;
+
+                    resumo.volume_concreto += volume;
+                    total_chapas_forma += chapas;
+                    total_madeira_forma += madeira;
+                    total_aco_macica += aco;
+
+                    resultado.push_str(&format!(
+                        "Espessura: {:.2} m\nÁrea do Ambiente: {:.2} m²\nVolume de Concreto: {:.2} m³\n\
+                        Chapas de Compensado ({:.2} x {:.2} m): {}\nSarrafos da Forma: {:.2} m\nAço: {:.2} kg\n",
+                        espessura_macica,
+                        area_ambiente,
+                        volume,
+                        LARGURA_CHAPA_COMPENSADO,
+                        COMPRIMENTO_CHAPA_COMPENSADO,
+                        chapas,
+                        madeira,
+                        aco
+                    ));
+
+                    (volume, Decimal::ZERO)
+                }
+                TipoLaje::Nervurada => {
+                    let cubeta = &CUBETAS_NERVURADA[self.cubeta_nervurada];
+                    let cubetas = Self::calc_cubetas(&lado_instalacao, &lado_perpendicular, cubeta);
+                    // Volume cheio (nervuras + capa) menos o vazio ocupado pelas fôrmas
+                    let volume = area_ambiente * (cubeta.altura + espessura_capa)
+                        - Decimal::from(cubetas) * cubeta.volume_vazio;
+
+                    resumo.volume_concreto += volume;
+                    *resumo.blocos.entry(cubeta.nome).or_insert(0) += cubetas;
+
+                    resultado.push_str(&format!(
+                        "Fôrma: {}\nCapa: {:.2} m\nÁrea do Ambiente: {:.2} m²\n{}: {}\nVolume de Concreto: {:.2} m³\n",
+                        cubeta.nome,
+                        espessura_capa,
+                        area_ambiente,
+                        ambiente.tipo_laje.nome_elementos().unwrap_or_default(),
+                        cubetas,
+                        volume
+                    ));
+
+                    (volume, Decimal::ZERO)
+                }
+                TipoLaje::Trelica | TipoLaje::Isopor => {
+                    // Acumular vigas por comprimento
+                    *beams_per_length.entry(comprimento_ajustado).or_insert(0) += num_vigas;
+
+                    let elementos = match ambiente.tipo_laje {
+                        TipoLaje::Trelica => Self::calc_trelica(&largura_ajustada, &comprimento_ajustado),
+                        TipoLaje::Isopor => Self::calc_isopor(&num_vigas, &comprimento_ajustado),
+                        TipoLaje::Macica | TipoLaje::Nervurada => 0,
+                    };
+                    let bloco = &BLOCOS_LAJE[ambiente.bloco];
+
+                    // Capa sobre toda a área mais o concreto que preenche a nervura entre os blocos
+                    let volume = area_ambiente * espessura_capa
+                        + Decimal::from(num_vigas)
+                            * comprimento_ajustado
+                            * LARGURA_NERVURA_VIGOTA
+                            * Decimal::from(bloco.altura)
+                            / dec!(100);
+
+                    *resumo.vigas.entry(comprimento_ajustado).or_insert(0) += num_vigas;
+                    *resumo.blocos.entry(bloco.nome).or_insert(0) += elementos;
+                    resumo.volume_concreto += volume;
+
+                    resultado.push_str(&format!(
+                        "Bloco: {}\nLargura Ajustada: {:.2} m\nVão Livre: {:.2} m + Apoios: 2 x {:.2} m\n\
+                        Comprimento Ajustado: {:.2} m\nVigas: {} de {:.2} m\n{}: {}\nÁrea do Ambiente: {:.2} m²\n",
+                        bloco.nome,
+                        largura_ajustada,
+                        lado_instalacao,
+                        apoio,
+                        comprimento_ajustado,
+                        num_vigas,
+                        comprimento_ajustado,
+                        ambiente.tipo_laje.nome_elementos().unwrap_or_default(),
+                        elementos,
+                        area_ambiente
+                    ));
+
+                    // As tabelas de vão das treliças são pelo vão livre, sem os apoios
+                    match Self::recomendar_trelica(&lado_instalacao, self.uso_laje) {
+                        Some(modelo) => resultado.push_str(&format!(
+                            "Treliça Recomendada: {} (laje H{}, vão máx. {:.2} m)\n",
+                            modelo.nome,
+                            modelo.altura_laje,
+                            modelo.vao_maximo(self.uso_laje)
+                        )),
+                        None => resultado.push_str(&format!(
+                            "ATENÇÃO: vão de {:.2} m excede o limite das treliças cadastradas ({:.2} m). \
+                            Prever viga intermediária ou consultar o fabricante.\n",
+                            lado_instalacao,
+                            MODELOS_TRELICA[MODELOS_TRELICA.len() - 1].vao_maximo(self.uso_laje)
+                        )),
                     }
+
+                    let peso_vigotas = Decimal::from(num_vigas)
+                        * comprimento_ajustado
+                        * SECAO_SAPATA_VIGOTA
+                        * PESO_ESPECIFICO_CONCRETO;
+                    (volume, Decimal::from(elementos) * bloco.peso * KN_POR_KG + peso_vigotas)
                 }
-                DirecaoLaje::MenorLado => {
-                    if largura_original <= comprimento_original {
-                        (largura_original, comprimento_original)
-                    } else {
-                        (comprimento_original, largura_original)
+            };
+
+            desenhos.push(DesenhoLaje {
+                ambiente: i + 1,
+                tipo: ambiente.tipo_laje,
+                lado_instalacao,
+                lado_perpendicular,
+                comprimento_ajustado,
+                largura_ajustada,
+                num_vigas,
+                modulo: match ambiente.tipo_laje {
+                    TipoLaje::Nervurada => Some(CUBETAS_NERVURADA[self.cubeta_nervurada].modulo),
+                    tipo => tipo.largura_step(),
+                },
+            });
+
+            if self.calcular_cargas {
+                let peso_proprio =
+                    (volume_ambiente * PESO_ESPECIFICO_CONCRETO + peso_pre_moldados) / area_ambiente;
+                let carga_permanente = peso_proprio + carga_revestimento;
+                let carga_total = carga_permanente + carga_acidental;
+                total_carga += carga_total * area_ambiente;
+
+                resultado.push_str(&format!(
+                    "Peso Próprio: {:.2} kN/m²\nCarga Permanente: {:.2} kN/m²\nCarga Acidental: {:.2} kN/m²\n\
+                    Carga Total: {:.2} kN/m² ({:.1} kN)\n",
+                    peso_proprio,
+                    carga_permanente,
+                    carga_acidental,
+                    carga_total,
+                    carga_total * area_ambiente
+                ));
+
+                match ambiente.tipo_laje {
+                    // Vigas armadas em uma direção descarregam nas duas paredes de apoio
+                    TipoLaje::Trelica | TipoLaje::Isopor => resultado.push_str(&format!(
+                        "Carga nas Paredes de Apoio: {:.2} kN/m (2 paredes de {:.2} m)\n",
+                        carga_total * lado_instalacao / dec!(2),
+                        lado_perpendicular
+                    )),
+                    // Lajes armadas em cruz: carga distribuída em todo o contorno
+                    TipoLaje::Macica | TipoLaje::Nervurada => resultado.push_str(&format!(
+                        "Carga no Contorno: {:.2} kN/m ({:.2} m de parede)\n",
+                        carga_total * area_ambiente / ((lado_instalacao + lado_perpendicular) * dec!(2)),
+                        (lado_instalacao + lado_perpendicular) * dec!(2)
+                    )),
+                }
+            }
+
+            // A laje maciça leva a armadura calculada pela taxa de aço
+            if self.calcular_armadura && ambiente.tipo_laje != TipoLaje::Macica {
+                match self.tipo_armadura {
+                    TipoArmadura::TelaSoldada => {
+                        let paineis = Self::calc_paineis_tela(
+                            &lado_instalacao,
+                            &lado_perpendicular,
+                            self.painel_tela,
+                            &transpasse,
+                        );
+                        total_paineis += paineis;
+                        resultado.push_str(&format!(
+                            "Tela {}: {} painéis\n",
+                            self.modelo_tela.nome(),
+                            paineis
+                        ));
+                    }
+                    TipoArmadura::Barras => {
+                        let (barras, metros) = Self::calc_barras_distribuicao(
+                            &lado_instalacao,
+                            &lado_perpendicular,
+                            &espacamento,
+                        );
+                        total_barras_metros += metros;
+                        resultado.push_str(&format!(
+                            "Barras de distribuição: {} de {:.2} m ({:.2} m)\n",
+                            barras, lado_perpendicular, metros
+                        ));
                     }
                 }
+            }
+
+            if self.calcular_escoramento {
+                let (linhas, escoras, guias) = Self::calc_escoramento(
+                    &lado_instalacao,
+                    &lado_perpendicular,
+                    &espacamento_linhas,
+                    &espacamento_escoras,
+                );
+                total_linhas_escora += linhas;
+                total_escoras += escoras;
+                total_guias_metros += guias;
+                resultado.push_str(&format!(
+                    "Escoramento: {} linhas, {} escoras, {:.2} m de guia\n",
+                    linhas, escoras, guias
+                ));
+            }
+
+            resultado.push('\n');
+        }
+
+        resultado.push_str(&format!(
+            "Área Total: {:.2} m²\nTotal de Vigas:\n",
+            total_area,
+        ));
+
+        // Exibir o total de vigas por comprimento
+        for (comprimento, quantidade) in beams_per_length.iter() {
+            resultado.push_str(&format!(
+                "  {} vigas de {:.2} m\n",
+                quantidade, comprimento
+            ));
+        }
+        if !beams_per_length.is_empty() {
+            resultado.push_str(&format!(
+                "  (comprimentos com apoio de {:.2} m em cada parede)\n",
+                apoio
+            ));
+        }
+
+        if self.calcular_cargas {
+            resultado.push_str(&format!(
+                "Carga Total Transmitida: {:.1} kN\n",
+                total_carga
+            ));
+        }
+
+        // Totais agrupados por tipo de laje
+        for resumo in &resumo_por_tipo {
+            resultado.push_str(&format!(
+                "\nLaje {}: {:.2} m²\n",
+                resumo.tipo.nome(),
+                resumo.area
+            ));
+            for (comprimento, quantidade) in resumo.vigas.iter() {
+                resultado.push_str(&format!(
+                    "  {} vigas de {:.2} m\n",
+                    quantidade, comprimento
+                ));
+            }
+            if let Some(nome_elementos) = resumo.tipo.nome_elementos() {
+                for (nome, quantidade) in resumo.blocos.iter() {
+                    resultado.push_str(&format!(
+                        "  {}: {} - {}\n",
+                        nome_elementos, quantidade, nome
+                    ));
+                }
+            }
+            if resumo.volume_concreto > Decimal::ZERO {
+                resultado.push_str(&format!(
+                    "  Concreto: {:.2} m³\n",
+                    resumo.volume_concreto
+                ));
+            }
+            if resumo.tipo == TipoLaje::Nervurada {
+                let total_cubetas: u32 = resumo.blocos.values().sum();
+                resultado.push_str(&format!(
+                    "  Cubetas para Locação: {} (concretagem única)\n",
+                    total_cubetas
+                ));
+            }
+            if resumo.tipo == TipoLaje::Macica {
+                resultado.push_str(&format!(
+                    "  Chapas de Compensado (concretagem única): {}\n  \
+                    Concretando em {} etapas: {} chapas por etapa, reaproveitadas nas seguintes\n",
+                    total_chapas_forma,
+                    reaproveitamento_forma,
+                    (Decimal::from(total_chapas_forma) / reaproveitamento_forma).ceil()
+                ));
+                resultado.push_str(&format!(
+                    "  Sarrafos da Forma: {:.2} m\n  Aço: {:.2} kg\n",
+                    total_madeira_forma, total_aco_macica
+                ));
+            }
+        }
+
+        if self.calcular_armadura {
+            match self.tipo_armadura {
+                TipoArmadura::TelaSoldada => {
+                    let (painel_largura, painel_comprimento) = self.painel_tela.dimensoes();
+                    let peso = Decimal::from(total_paineis)
+                        * painel_largura
+                        * painel_comprimento
+                        * self.modelo_tela.peso_m2();
+                    resultado.push_str(&format!(
+                        "\nArmadura de Distribuição:\nTela {} ({:.2} x {:.2} m, transpasse {:.2} m): {} painéis\n\
+                        Peso de Aço: {:.2} kg\n",
+                        self.modelo_tela.nome(),
+                        painel_largura,
+                        painel_comprimento,
+                        transpasse,
+                        total_paineis,
+                        peso
+                    ));
+                }
+                TipoArmadura::Barras => {
+                    let barras_comerciais = (total_barras_metros / COMPRIMENTO_BARRA_ACO).ceil();
+                    let peso = total_barras_metros * diametro * diametro * FATOR_MASSA_LINEAR_ACO;
+                    resultado.push_str(&format!(
+                        "\nArmadura de Distribuição:\nBarras Ø{:.1} mm a cada {:.2} m: {:.2} m\n\
+                        Barras de {} m: {}\nPeso de Aço: {:.2} kg\n",
+                        diametro,
+                        espacamento,
+                        total_barras_metros,
+                        COMPRIMENTO_BARRA_ACO,
+                        barras_comerciais,
+                        peso
+                    ));
+                }
+            }
+        }
+
+        if self.calcular_plano_corte {
+            let mut cortes = Vec::new();
+            for (comprimento, quantidade) in beams_per_length.iter() {
+                if *comprimento > comprimento_estoque {
+                    self.resultado = format!(
+                        "Viga de {:.2} m maior que a barra de estoque de {:.2} m",
+                        comprimento, comprimento_estoque
+                    );
+                    return;
+                }
+                for _ in 0..*quantidade {
+                    cortes.push(*comprimento);
+                }
+            }
+
+            let barras = Self::plano_de_corte(&cortes, comprimento_estoque, &sobras);
+            resultado.push_str(&format!(
+                "\nPlano de Corte (barras de {:.2} m):\n",
+                comprimento_estoque
+            ));
+
+            let mut barras_novas = 0u32;
+            let mut sobra_total = Decimal::ZERO;
+            let mut comprimento_usado = Decimal::ZERO;
+            for barra in &barras {
+                if !barra.reaproveitada {
+                    barras_novas += 1;
+                }
+                sobra_total += barra.sobra();
+                comprimento_usado += barra.comprimento;
+
+                let cortes_texto: Vec<String> =
+                    barra.cortes.iter().map(|c| format!("{:.2}", c)).collect();
+                resultado.push_str(&format!(
+                    "  {} de {:.2} m: {} | sobra {:.2} m\n",
+                    if barra.reaproveitada { "Sobra" } else { "Barra" },
+                    barra.comprimento,
+                    cortes_texto.join(" + "),
+                    barra.sobra()
+                ));
+            }
+
+            let percentual = if comprimento_usado > Decimal::ZERO {
+                sobra_total / comprimento_usado * dec!(100)
+            } else {
+                Decimal::ZERO
             };
+            resultado.push_str(&format!(
+                "Barras Novas Necessárias: {}\nSobra Total: {:.2} m ({:.1}%)\n",
+                barras_novas, sobra_total, percentual
+            ));
+        }
+
+        if self.calcular_escoramento {
+            resultado.push_str(&format!(
+                "\nEscoramento:\nLinhas de Escoramento: {}\nEscoras {}: {}\nGuias de Madeira: {:.2} m\n",
+                total_linhas_escora,
+                if self.tipo_escora == TipoEscora::Metalica {
+                    "Metálicas"
+                } else {
+                    "de Madeira"
+                },
+                total_escoras,
+                total_guias_metros
+            ));
 
-            let largura_step = match self.tipo_laje {
-                TipoLaje::Trelica => LARGURA_TRELICA,
-                TipoLaje::Isopor => LARGURA_ISOPOR,
-            };
+            if let Some((diaria, dias)) = locacao {
+                let custo = Decimal::from(total_escoras) * diaria * dias;
+                resultado.push_str(&format!(
+                    "Locação: {} escoras x {} dias x R$ {:.2} = R$ {:.2}\n",
+                    total_escoras, dias, diaria, custo
+                ));
+            }
+        }
 
-            let comprimento_ajustado = Self::ajustar_dimensao(&lado_instalacao, &COMPRIMENTO_STEP);
-            let (largura_ajustada, num_vigas) = Self::reajuste(&lado_perpendicular, &largura_step);
+        self.resultado = resultado;
+        self.desenhos = desenhos;
+    }
 
-            // Acumular vigas por comprimento
-            *beams_per_length.entry(comprimento_ajustado).or_insert(0) += num_vigas;
+    // Grava todos os ambientes calculados, um abaixo do outro, em um arquivo SVG
+    fn exportar_svg(&mut self) {
+        if self.desenhos.is_empty() {
+            self.mensagem_exportacao = "Calcule a laje antes de exportar o desenho".to_string();
+            return;
+        }
 
-            let elementos = match self.tipo_laje {
-                TipoLaje::Trelica => Self::calc_trelica(&largura_ajustada, &comprimento_ajustado),
-                TipoLaje::Isopor => Self::calc_isopor(&num_vigas, &comprimento_ajustado),
-            };
+        let formas: Vec<(String, Vec<FormaDesenho>)> = self
+            .desenhos
+            .iter()
+            .map(|desenho| (format!("Ambiente {}", desenho.ambiente), desenho.formas()))
+            .collect();
+        let mut caminho = self.arquivo_svg.trim().to_string();
+        if caminho.is_empty() {
+            self.mensagem_exportacao = "Informe o nome do arquivo do desenho".to_string();
+            return;
+        }
+        if !caminho.to_lowercase().ends_with(".svg") {
+            caminho.push_str(".svg");
+        }
+        self.arquivo_svg = caminho.clone();
 
-            let area_ambiente = largura_original * comprimento_original;
-            total_area += area_ambiente;
-            total_elementos += elementos;
+        // Um arquivo existente só é substituído se o usuário clicar de novo
+        if std::path::Path::new(&caminho).exists()
+            && self.confirmar_sobrescrita.as_deref() != Some(caminho.as_str())
+        {
+            self.mensagem_exportacao = format!(
+                "O arquivo {} já existe. Clique em Exportar novamente para substituí-lo.",
+                caminho
+            );
+            self.confirmar_sobrescrita = Some(caminho);
+            return;
+        }
+        self.confirmar_sobrescrita = None;
 
-            resultado.push_str(&format!(
-                "Ambiente {}:\nTipo de Laje: {}\nDireção de instalação: {}\nLargura Ajustada: {:.2} m\nComprimento Ajustado: {:.2} m\n\
-Vigas: {} de {:.2} m\n{}: {}\nÁrea do Ambiente: {:.2} m²\n\n",
-                i + 1,
-                if self.tipo_laje == TipoLaje::Trelica {
-                    "Treliça"
-                } else {
-                    "Isopor"
-                },
-                if ambiente.direcao == DirecaoLaje::MenorLado {
-                    "Menor lado"
+        self.mensagem_exportacao = match std::fs::write(&caminho, formas_para_svg(&formas)) {
+            Ok(()) => format!("Desenho salvo em {}", caminho),
+            Err(e) => format!("Erro ao salvar o desenho: {}", e),
+        };
+    }
+
+    fn layout(
+        largura: &Decimal,
+        comprimento: &Decimal,
+        direcao: DirecaoLaje,
+        largura_step: Option<&Decimal>,
+        apoio: &Decimal,
+    ) -> LayoutLaje {
+        let (lado_instalacao, lado_perpendicular) = match direcao {
+            DirecaoLaje::MaiorLado => {
+                if largura >= comprimento {
+                    (*largura, *comprimento)
                 } else {
-                    "Maior lado"
-                },
-                largura_ajustada,
-                comprimento_ajustado,
-                num_vigas,
-                comprimento_ajustado,
-                if self.tipo_laje == TipoLaje::Trelica {
-                    "Lajotas"
+                    (*comprimento, *largura)
+                }
+            }
+            DirecaoLaje::MenorLado | DirecaoLaje::Automatico => {
+                if largura <= comprimento {
+                    (*largura, *comprimento)
                 } else {
-                    "Placas de isopor"
-                },
-                elementos,
-                area_ambiente
-            ));
-        }
+                    (*comprimento, *largura)
+                }
+            }
+        };
 
-        resultado.push_str(&format!(
-            "Área Total: {:.2} m²\nTotal de Vigas:\n",
-            total_area,
-        ));
+        // A viga vence o vão livre e ainda apoia nas duas paredes
+        let comprimento_ajustado = Self::ajustar_dimensao(
+            &(lado_instalacao + apoio * dec!(2)),
+            &COMPRIMENTO_STEP,
+        );
+        let (largura_ajustada, num_vigas) = match largura_step {
+            Some(step) => Self::reajuste(&lado_perpendicular, step),
+            None => (lado_perpendicular, 0),
+        };
 
-        // Exibir o total de vigas por comprimento
-        for (comprimento, quantidade) in beams_per_length.iter() {
-            resultado.push_str(&format!(
-                "  {} vigas de {:.2} m\n",
-                quantidade, comprimento
-            ));
+        LayoutLaje {
+            direcao,
+            lado_instalacao,
+            lado_perpendicular,
+            comprimento_ajustado,
+            largura_ajustada,
+            num_vigas,
         }
+    }
 
-        resultado.push_str(&format!(
-            "Total de {}: {}\n",
-            if self.tipo_laje == TipoLaje::Trelica {
-                "Lajotas"
-            } else {
-                "Placas de isopor"
-            },
-            total_elementos
-        ));
-
-        self.resultado = resultado;
+    // Prefere a direção cujo vão é atendido por alguma treliça; depois, a que
+    // consome menos metros de viga e, por fim, a que usa menos vigas
+    fn melhor_layout(a: LayoutLaje, b: LayoutLaje, uso: UsoLaje) -> LayoutLaje {
+        let criterio = |layout: &LayoutLaje| {
+            (
+                Self::recomendar_trelica(&layout.lado_instalacao, uso).is_none(),
+                layout.metros_vigas(),
+                layout.num_vigas,
+            )
+        };
+        if criterio(&b) < criterio(&a) {
+            b
+        } else {
+            a
+        }
     }
 
     fn ajustar_dimensao(dimensao: &Decimal, step: &Decimal) -> Decimal {
@@ -1351,6 +5834,131 @@ Vigas: {} de {:.2} m\n{}: {}\nÁrea do Ambiente: {:.2} m²\n\n",
         (count * div, count.to_u32().unwrap_or(0))
     }
 
+    // Menor modelo de treliça que vence o vão para o uso informado
+    fn recomendar_trelica(vao: &Decimal, uso: UsoLaje) -> Option<&'static ModeloTrelica> {
+        MODELOS_TRELICA
+            .iter()
+            .find(|modelo| modelo.vao_maximo(uso) >= *vao)
+    }
+
+    fn calc_paineis_tela(
+        largura: &Decimal,
+        comprimento: &Decimal,
+        painel: PainelTela,
+        transpasse: &Decimal,
+    ) -> u32 {
+        let (painel_largura, painel_comprimento) = painel.dimensoes();
+        let (menor, maior) = if largura <= comprimento {
+            (largura, comprimento)
+        } else {
+            (comprimento, largura)
+        };
+
+        // Cada painel adicional cobre seu tamanho menos o transpasse com o anterior
+        let paineis_no_lado = |dimensao: &Decimal, tamanho: Decimal| -> Decimal {
+            if *dimensao <= tamanho {
+                Decimal::ONE
+            } else {
+                ((dimensao - tamanho) / (tamanho - transpasse)).ceil() + Decimal::ONE
+            }
+        };
+
+        (paineis_no_lado(menor, painel_largura) * paineis_no_lado(maior, painel_comprimento))
+            .to_u32()
+            .unwrap_or(0)
+    }
+
+    fn calc_barras_distribuicao(
+        lado_instalacao: &Decimal,
+        lado_perpendicular: &Decimal,
+        espacamento: &Decimal,
+    ) -> (u32, Decimal) {
+        // Barras atravessam as vigas, distribuídas ao longo do comprimento delas
+        let barras = (lado_instalacao / espacamento).ceil() + Decimal::ONE;
+        (barras.to_u32().unwrap_or(0), barras * lado_perpendicular)
+    }
+
+    // Best Fit Decreasing: cada viga, da maior para a menor, vai para a barra aberta
+    // que deixa a menor sobra; as sobras informadas entram como barras já abertas.
+    fn plano_de_corte(
+        cortes: &[Decimal],
+        comprimento_estoque: Decimal,
+        sobras: &[Decimal],
+    ) -> Vec<BarraCorte> {
+        let mut cortes = cortes.to_vec();
+        cortes.sort_by(|a, b| b.cmp(a));
+
+        let mut barras: Vec<BarraCorte> = sobras
+            .iter()
+            .map(|&comprimento| BarraCorte {
+                comprimento,
+                reaproveitada: true,
+                cortes: Vec::new(),
+            })
+            .collect();
+
+        for corte in cortes {
+            let melhor = barras
+                .iter()
+                .enumerate()
+                .filter(|(_, barra)| barra.sobra() >= corte)
+                .min_by_key(|(_, barra)| barra.sobra())
+                .map(|(indice, _)| indice);
+
+            match melhor {
+                Some(indice) => barras[indice].cortes.push(corte),
+                None => barras.push(BarraCorte {
+                    comprimento: comprimento_estoque,
+                    reaproveitada: false,
+                    cortes: vec![corte],
+                }),
+            }
+        }
+
+        // Sobras que não receberam nenhum corte continuam no estoque
+        barras.retain(|barra| !barra.cortes.is_empty());
+        barras
+    }
+
+    fn calc_escoramento(
+        lado_instalacao: &Decimal,
+        lado_perpendicular: &Decimal,
+        espacamento_linhas: &Decimal,
+        espacamento_escoras: &Decimal,
+    ) -> (u32, u32, Decimal) {
+        // As linhas de guia cruzam as vigas; as paredes de apoio dispensam linhas nas pontas
+        let linhas = ((lado_instalacao / espacamento_linhas).ceil() - Decimal::ONE).max(Decimal::ZERO);
+        let escoras_por_linha = (lado_perpendicular / espacamento_escoras).ceil();
+        (
+            linhas.to_u32().unwrap_or(0),
+            (linhas * escoras_por_linha).to_u32().unwrap_or(0),
+            linhas * lado_perpendicular,
+        )
+    }
+
+    // Só cabem fôrmas inteiras; a faixa que sobra junto às paredes fica maciça
+    fn calc_cubetas(lado_instalacao: &Decimal, lado_perpendicular: &Decimal, cubeta: &Cubeta) -> u32 {
+        ((lado_instalacao / cubeta.modulo).floor() * (lado_perpendicular / cubeta.modulo).floor())
+            .to_u32()
+            .unwrap_or(0)
+    }
+
+    // Chapas inteiras em cada lado, na orientação que usa menos chapas
+    fn calc_chapas_forma(lado_instalacao: &Decimal, lado_perpendicular: &Decimal) -> u32 {
+        let chapas = |lado_a: Decimal, lado_b: Decimal| {
+            (lado_instalacao / lado_a).ceil() * (lado_perpendicular / lado_b).ceil()
+        };
+        chapas(LARGURA_CHAPA_COMPENSADO, COMPRIMENTO_CHAPA_COMPENSADO)
+            .min(chapas(COMPRIMENTO_CHAPA_COMPENSADO, LARGURA_CHAPA_COMPENSADO))
+            .to_u32()
+            .unwrap_or(0)
+    }
+
+    // Sarrafos sob as chapas, paralelos ao lado de instalação
+    fn calc_madeira_forma(lado_instalacao: &Decimal, lado_perpendicular: &Decimal) -> Decimal {
+        ((lado_perpendicular / ESPACAMENTO_SARRAFO_FORMA).ceil() + Decimal::ONE) * lado_instalacao
+    }
+
     fn calc_trelica(largura: &Decimal, comprimento: &Decimal) -> u32 {
         (largura * comprimento * Decimal::from(13))
             .ceil()
@@ -1456,6 +6064,18 @@ impl CalculadoraMateriais {
         (Some(volume), Some(cimento), Some(areia), Some(pedra))
     }
 
+    fn sacos_cimento(cimento: Decimal) -> Decimal {
+        (cimento * PESO_AREIA_POR_M3 / PESO_SACO_CIMENTO).ceil()
+    }
+
+    // Areia ou pedra: m³ arredondado para meio metro e sacos de 20kg
+    fn agregado_m3_e_sacos(volume: Decimal) -> (Decimal, Decimal) {
+        (
+            (volume * dec!(2)).ceil() / dec!(2),
+            (volume * PESO_AREIA_POR_M3 / PESO_SACO_AREIA).ceil(),
+        )
+    }
+
     fn proporcao_total(&self) -> Decimal {
         Decimal::from_str(&self.proporcao_cimento).unwrap_or(dec!(1))
             + Decimal::from_str(&self.proporcao_areia).unwrap_or(dec!(2))
@@ -1469,11 +6089,9 @@ impl CalculadoraMateriais {
         areia: Decimal,
         pedra: Decimal,
     ) {
-        let cimento_sacos = (cimento * PESO_AREIA_POR_M3 / PESO_SACO_CIMENTO).ceil();
-        let areia_m3 = (areia * dec!(2)).ceil() / dec!(2);
-        let areia_sacos = (areia * PESO_AREIA_POR_M3 / PESO_SACO_AREIA).ceil();
-        let pedra_m3 = (pedra * dec!(2)).ceil() / dec!(2);
-        let pedra_sacos = (pedra * PESO_AREIA_POR_M3 / PESO_SACO_AREIA).ceil();
+        let cimento_sacos = Self::sacos_cimento(cimento);
+        let (areia_m3, areia_sacos) = Self::agregado_m3_e_sacos(areia);
+        let (pedra_m3, pedra_sacos) = Self::agregado_m3_e_sacos(pedra);
 
         self.resultado = format!(
             "Volume de concreto: {:.2} m³\n\
@@ -1490,7 +6108,7 @@ impl CalculadoraMateriais {
             pedra_m3,
             pedra_sacos,
             PESO_SACO_AREIA,
-            cimento / cimento,
+            Decimal::ONE,
             areia / cimento,
             pedra / cimento
         );
@@ -1598,9 +6216,11 @@ impl CalculadoraForro {
         let mut total_acabamento = Decimal::ZERO;
         let mut total_emenda_metros = Decimal::ZERO;
         let tamanho_emenda_barra = dec!(6); // Comprimento das barras de emenda
-        let mut total_pecas = [0u32; 4]; // [3m, 4m, 5m, 6m]
         let tamanhos = [dec!(3), dec!(4), dec!(5), dec!(6)];
 
+        // Primeiro as medidas e a direção de cada ambiente; o corte é planejado para todos juntos
+        let mut medidas = Vec::new();
+        let mut fileiras = Vec::new();
         for (i, ambiente) in self.ambientes.iter().enumerate() {
             let largura = match parse_decimal(&ambiente.largura) {
                 Ok(v) => v,
@@ -1618,66 +6238,56 @@ impl CalculadoraForro {
                 }
             };
 
-            let area = largura * comprimento;
-            total_area += area;
-
-            let perimeter = (largura + comprimento) * dec!(2);
-            total_acabamento += perimeter;
-
-            let (lado_instalacao, lado_perpendicular) = match self.direcao_global {
-                DirecaoForro::MaiorLado => {
-                    if largura >= comprimento {
-                        (largura, comprimento)
-                    } else {
-                        (comprimento, largura)
-                    }
-                }
-                DirecaoForro::MenorLado => {
-                    if largura <= comprimento {
-                        (largura, comprimento)
-                    } else {
-                        (comprimento, largura)
-                    }
+            let mut comparacao = String::new();
+            let direcao = match self.direcao_global {
+                DirecaoForro::Automatico => {
+                    let opcoes = [DirecaoForro::MaiorLado, DirecaoForro::MenorLado].map(|direcao| {
+                        let plano =
+                            Self::planejar_cortes(&[Self::fileiras(0, &largura, &comprimento, direcao)], 1, &tamanhos);
+                        (direcao, plano.juntas[0], plano.desperdicio[0])
+                    });
+                    let textos: Vec<String> = opcoes
+                        .iter()
+                        .map(|(direcao, juntas, desperdicio)| {
+                            format!(
+                                "{} = {:.2} m de sobra, {} emendas",
+                                direcao.nome(),
+                                desperdicio,
+                                juntas
+                            )
+                        })
+                        .collect();
+                    comparacao = format!("Comparação: {}\n", textos.join(" | "));
+
+                    // Menor desperdício de PVC; em caso de empate, menos emendas
+                    let (direcao, _, _) = opcoes
+                        .into_iter()
+                        .min_by_key(|(_, juntas, desperdicio)| (*desperdicio, *juntas))
+                        .unwrap();
+                    direcao
                 }
+                direcao => direcao,
             };
 
-            // Calcular o número de fileiras (considerando a largura de 20cm)
-            let num_fileiras = (lado_perpendicular / dec!(0.2)).ceil().to_u32().unwrap_or(0);
-
-            let mut pecas_ambiente = [0u32; 4];
-            let mut total_juntas = 0u32;
-
-            for _ in 0..num_fileiras {
-                let mut comprimento_restante = lado_instalacao;
-                let mut num_pecas_por_fileira = 0u32;
+            fileiras.push(Self::fileiras(i, &largura, &comprimento, direcao));
+            medidas.push((largura, comprimento, direcao, comparacao));
+        }
 
-                while comprimento_restante > Decimal::ZERO {
-                    // Encontrar o maior tamanho possível que seja menor ou igual ao comprimento restante
-                    let mut melhor_tamanho = None;
-                    for &tamanho in tamanhos.iter().rev() {
-                        if tamanho <= comprimento_restante {
-                            melhor_tamanho = Some(tamanho);
-                            break;
-                        }
-                    }
+        let plano = Self::planejar_cortes(&fileiras, medidas.len(), &tamanhos);
+        let mut total_pecas = [0u32; 4]; // [3m, 4m, 5m, 6m]
 
-                    // Se não houver tamanho adequado, usar o menor tamanho disponível
-                    let melhor_tamanho = melhor_tamanho.unwrap_or(tamanhos[0]);
+        for (i, (largura, comprimento, direcao, comparacao)) in medidas.iter().enumerate() {
+            let area = largura * comprimento;
+            total_area += area;
 
-                    let indice = tamanhos.iter().position(|&t| t == melhor_tamanho).unwrap();
-                    pecas_ambiente[indice] += 1;
-                    total_pecas[indice] += 1;
-                    comprimento_restante -= melhor_tamanho;
-                    num_pecas_por_fileira += 1;
-                }
+            let perimeter = (largura + comprimento) * dec!(2);
+            total_acabamento += perimeter;
 
-                if num_pecas_por_fileira > 1 {
-                    // Cada junção precisa de emenda
-                    total_juntas += num_pecas_por_fileira - 1;
-                }
+            for (indice, &peca) in plano.pecas[i].iter().enumerate() {
+                total_pecas[indice] += peca;
             }
 
-            let emenda_metros_ambiente = Decimal::from(total_juntas) * dec!(0.2);
+            let emenda_metros_ambiente = Decimal::from(plano.juntas[i]) * dec!(0.2);
 
             total_emenda_metros += emenda_metros_ambiente;
 
@@ -1690,20 +6300,34 @@ impl CalculadoraForro {
             ));
             resultado.push_str(&format!("Perímetro: {:.2}m\n", perimeter));
             resultado.push_str(&format!(
-                "Direção de instalação: {}\n",
-                if self.direcao_global == DirecaoForro::MaiorLado {
-                    "Maior lado"
+                "Direção de instalação: {}\n{}",
+                if self.direcao_global == DirecaoForro::Automatico {
+                    format!("Automático ({})", direcao.nome())
                 } else {
-                    "Menor lado"
-                }
+                    direcao.nome().to_string()
+                },
+                comparacao
             ));
 
-            for (i, &peca) in pecas_ambiente.iter().enumerate() {
+            for (i, &peca) in plano.pecas[i].iter().enumerate() {
                 if peca > 0 {
                     resultado.push_str(&format!("  Peças de {}m: {}\n", tamanhos[i], peca));
                 }
             }
 
+            if plano.reaproveitado[i] > Decimal::ZERO {
+                resultado.push_str(&format!(
+                    "  Reaproveitado de sobras: {:.2} metros\n",
+                    plano.reaproveitado[i]
+                ));
+            }
+
+            resultado.push_str(&format!(
+                "  Desperdício: {:.2} metros ({:.1}%)\n",
+                plano.desperdicio[i],
+                Self::percentual(plano.desperdicio[i], plano.comprado[i])
+            ));
+
             if emenda_metros_ambiente > Decimal::ZERO {
                 resultado.push_str(&format!(
                     "  Quantidade de emenda necessária: {:.2} metros\n",
@@ -1717,7 +6341,7 @@ impl CalculadoraForro {
                 "  Quantidade de acabamento: {:.2} metros\n",
                 perimeter
             ));
-            resultado.push_str("\n");
+            resultado.push('\n');
         }
 
         // Calcular quantidade de barras de emenda
@@ -1734,6 +6358,15 @@ impl CalculadoraForro {
             }
         }
 
+        let total_comprado: Decimal = plano.comprado.iter().sum();
+        let total_desperdicio: Decimal = plano.desperdicio.iter().sum();
+        resultado.push_str(&format!(
+            "\nDesperdício total: {:.2} metros ({:.1}% de {:.2} metros comprados)\n",
+            total_desperdicio,
+            Self::percentual(total_desperdicio, total_comprado),
+            total_comprado
+        ));
+
         if total_emenda_metros > Decimal::ZERO {
             resultado.push_str(&format!(
                 "\nTotal de emenda necessária: {:.2} metros\n",
@@ -1755,6 +6388,294 @@ impl CalculadoraForro {
 
         self.resultado = resultado;
     }
+
+    // Fileiras de um ambiente (largura da régua de 20cm): (ambiente, comprimento, quantidade)
+    fn fileiras(
+        ambiente: usize,
+        largura: &Decimal,
+        comprimento: &Decimal,
+        direcao: DirecaoForro,
+    ) -> (usize, Decimal, u32) {
+        let (lado_instalacao, lado_perpendicular) = Self::lados(largura, comprimento, direcao);
+        let quantidade = (lado_perpendicular / dec!(0.2)).ceil().to_u32().unwrap_or(0);
+        (ambiente, lado_instalacao, quantidade)
+    }
+
+    fn percentual(parte: Decimal, total: Decimal) -> Decimal {
+        if total.is_zero() {
+            Decimal::ZERO
+        } else {
+            parte / total * dec!(100)
+        }
+    }
+
+    fn lados(largura: &Decimal, comprimento: &Decimal, direcao: DirecaoForro) -> (Decimal, Decimal) {
+        match direcao {
+            DirecaoForro::MaiorLado | DirecaoForro::Automatico => {
+                if largura >= comprimento {
+                    (*largura, *comprimento)
+                } else {
+                    (*comprimento, *largura)
+                }
+            }
+            DirecaoForro::MenorLado => {
+                if largura <= comprimento {
+                    (*largura, *comprimento)
+                } else {
+                    (*comprimento, *largura)
+                }
+            }
+        }
+    }
+
+    // Best Fit Decreasing sobre os trechos de todas as fileiras: fileiras maiores que a
+    // maior régua são emendadas em réguas inteiras mais um trecho final. Cada trecho, do
+    // maior para o menor, sai da sobra que deixa o menor resto ou da menor régua nova
+    // que o comporta; o resto da régua volta para as sobras e pode servir a outro ambiente.
+    fn planejar_cortes(
+        fileiras: &[(usize, Decimal, u32)],
+        ambientes: usize,
+        tamanhos: &[Decimal; 4],
+    ) -> PlanoForro {
+        let maior = tamanhos[tamanhos.len() - 1];
+        let mut plano = PlanoForro {
+            pecas: vec![[0u32; 4]; ambientes],
+            juntas: vec![0; ambientes],
+            comprado: vec![Decimal::ZERO; ambientes],
+            reaproveitado: vec![Decimal::ZERO; ambientes],
+            desperdicio: vec![Decimal::ZERO; ambientes],
+        };
+
+        let mut trechos = Vec::new();
+        for &(ambiente, comprimento, quantidade) in fileiras {
+            if comprimento <= Decimal::ZERO {
+                continue;
+            }
+            let inteiras = ((comprimento / maior).ceil() - Decimal::ONE).to_u32().unwrap_or(0);
+            let final_fileira = comprimento - Decimal::from(inteiras) * maior;
+            for _ in 0..quantidade {
+                for _ in 0..inteiras {
+                    trechos.push((ambiente, maior));
+                }
+                trechos.push((ambiente, final_fileira));
+            }
+            plano.juntas[ambiente] += inteiras * quantidade;
+        }
+        trechos.sort_by_key(|(_, comprimento)| std::cmp::Reverse(*comprimento));
+
+        let mut sobras: Vec<SobraForro> = Vec::new();
+        for (ambiente, trecho) in trechos {
+            let melhor = sobras
+                .iter()
+                .enumerate()
+                .filter(|(_, sobra)| sobra.comprimento >= trecho)
+                .min_by_key(|(_, sobra)| sobra.comprimento)
+                .map(|(indice, _)| indice);
+
+            match melhor {
+                Some(indice) => {
+                    sobras[indice].comprimento -= trecho;
+                    plano.reaproveitado[ambiente] += trecho;
+                }
+                None => {
+                    let indice = tamanhos.iter().position(|t| *t >= trecho).unwrap_or(tamanhos.len() - 1);
+                    plano.pecas[ambiente][indice] += 1;
+                    plano.comprado[ambiente] += tamanhos[indice];
+                    sobras.push(SobraForro {
+                        comprimento: tamanhos[indice] - trecho,
+                        ambiente,
+                    });
+                }
+            }
+        }
+
+        // O que não foi usado é desperdício do ambiente que abriu a régua
+        for sobra in sobras {
+            plano.desperdicio[sobra.ambiente] += sobra.comprimento;
+        }
+
+        plano
+    }
+}
+
+// Limites (min_x, min_y, max_x, max_y) das formas, com folga para os textos
+fn limites_formas(formas: &[FormaDesenho]) -> (f32, f32, f32, f32) {
+    let mut limites = (0.0f32, 0.0f32, 0.0f32, 0.0f32);
+    let mut incluir = |x: f32, y: f32| {
+        limites.0 = limites.0.min(x);
+        limites.1 = limites.1.min(y);
+        limites.2 = limites.2.max(x);
+        limites.3 = limites.3.max(y);
+    };
+    for forma in formas {
+        match forma {
+            FormaDesenho::Retangulo { x, y, largura, altura, .. } => {
+                incluir(*x, *y);
+                incluir(x + largura, y + altura);
+            }
+            FormaDesenho::Linha { x1, y1, x2, y2, .. } => {
+                incluir(*x1, *y1);
+                incluir(*x2, *y2);
+            }
+            FormaDesenho::Poligono { pontos, .. } => {
+                for (x, y) in pontos {
+                    incluir(*x, *y);
+                }
+            }
+            FormaDesenho::Texto { x, y, .. } => incluir(*x, *y),
+        }
+    }
+    let folga = 0.3;
+    (limites.0 - folga, limites.1 - folga, limites.2 + folga, limites.3 + folga)
+}
+
+fn cor_egui(cor: [u8; 3]) -> Color32 {
+    Color32::from_rgb(cor[0], cor[1], cor[2])
+}
+
+fn desenhar_formas(ui: &mut egui::Ui, formas: &[FormaDesenho]) {
+    let (min_x, min_y, max_x, max_y) = limites_formas(formas);
+    let largura_util = ui.available_width().min(800.0);
+    let escala = largura_util / (max_x - min_x).max(0.1);
+    let (resposta, painter) = ui.allocate_painter(
+        egui::Vec2::new(largura_util, (max_y - min_y) * escala),
+        egui::Sense::hover(),
+    );
+    let origem = resposta.rect.min;
+    let ponto = |x: f32, y: f32| origem + egui::Vec2::new((x - min_x) * escala, (y - min_y) * escala);
+
+    for forma in formas {
+        match forma {
+            FormaDesenho::Retangulo { x, y, largura, altura, cor, preenchido } => {
+                let retangulo = egui::Rect::from_min_max(ponto(*x, *y), ponto(x + largura, y + altura));
+                if *preenchido {
+                    painter.rect_filled(retangulo, 0.0, cor_egui(*cor));
+                } else {
+                    painter.rect_stroke(retangulo, 0.0, egui::Stroke::new(2.0, cor_egui(*cor)));
+                }
+            }
+            FormaDesenho::Linha { x1, y1, x2, y2, cor, espessura } => {
+                painter.line_segment(
+                    [ponto(*x1, *y1), ponto(*x2, *y2)],
+                    egui::Stroke::new(*espessura, cor_egui(*cor)),
+                );
+            }
+            FormaDesenho::Poligono { pontos, cor, preenchido } => {
+                let pontos: Vec<egui::Pos2> = pontos.iter().map(|(x, y)| ponto(*x, *y)).collect();
+                if *preenchido {
+                    painter.add(egui::Shape::convex_polygon(pontos, cor_egui(*cor), egui::Stroke::NONE));
+                } else {
+                    painter.add(egui::Shape::closed_line(pontos, egui::Stroke::new(1.0, cor_egui(*cor))));
+                }
+            }
+            FormaDesenho::Texto { x, y, texto } => {
+                painter.text(
+                    ponto(*x, *y),
+                    egui::Align2::CENTER_CENTER,
+                    texto,
+                    egui::FontId::proportional(13.0),
+                    Color32::WHITE,
+                );
+            }
+        }
+    }
+}
+
+fn escapar_xml(texto: &str) -> String {
+    texto
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Desenhos empilhados verticalmente, na escala de 100 px por metro
+fn formas_para_svg(desenhos: &[(String, Vec<FormaDesenho>)]) -> String {
+    let escala = 100.0f32;
+    let mut corpo = String::new();
+    let mut deslocamento_y = 0.0f32;
+    let mut largura_total = 0.0f32;
+
+    for (titulo, formas) in desenhos {
+        let (min_x, min_y, max_x, max_y) = limites_formas(formas);
+        let dx = -min_x;
+        let dy = deslocamento_y + 0.5 - min_y;
+        let px = |valor: f32| valor * escala;
+        let rgb = |cor: &[u8; 3]| format!("rgb({},{},{})", cor[0], cor[1], cor[2]);
+
+        corpo.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"16\" font-weight=\"bold\">{}</text>\n",
+            px(0.1),
+            px(deslocamento_y + 0.3),
+            escapar_xml(titulo)
+        ));
+        for forma in formas {
+            match forma {
+                FormaDesenho::Retangulo { x, y, largura, altura, cor, preenchido } => {
+                    let estilo = if *preenchido {
+                        format!("fill=\"{}\"", rgb(cor))
+                    } else {
+                        format!("fill=\"none\" stroke=\"{}\" stroke-width=\"2\"", rgb(cor))
+                    };
+                    corpo.push_str(&format!(
+                        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" {}/>\n",
+                        px(x + dx),
+                        px(y + dy),
+                        px(*largura),
+                        px(*altura),
+                        estilo
+                    ));
+                }
+                FormaDesenho::Linha { x1, y1, x2, y2, cor, espessura } => {
+                    corpo.push_str(&format!(
+                        "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\" stroke-width=\"{:.1}\"/>\n",
+                        px(x1 + dx),
+                        px(y1 + dy),
+                        px(x2 + dx),
+                        px(y2 + dy),
+                        rgb(cor),
+                        espessura
+                    ));
+                }
+                FormaDesenho::Poligono { pontos, cor, preenchido } => {
+                    let pontos: Vec<String> = pontos
+                        .iter()
+                        .map(|(x, y)| format!("{:.1},{:.1}", px(x + dx), px(y + dy)))
+                        .collect();
+                    let estilo = if *preenchido {
+                        format!("fill=\"{}\"", rgb(cor))
+                    } else {
+                        format!("fill=\"none\" stroke=\"{}\" stroke-width=\"1\"", rgb(cor))
+                    };
+                    corpo.push_str(&format!(
+                        "<polygon points=\"{}\" {}/>\n",
+                        pontos.join(" "),
+                        estilo
+                    ));
+                }
+                FormaDesenho::Texto { x, y, texto } => {
+                    corpo.push_str(&format!(
+                        "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"14\" text-anchor=\"middle\" \
+                        dominant-baseline=\"middle\">{}</text>\n",
+                        px(x + dx),
+                        px(y + dy),
+                        escapar_xml(texto)
+                    ));
+                }
+            }
+        }
+
+        largura_total = largura_total.max(max_x - min_x);
+        deslocamento_y = dy + max_y + 0.5;
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" font-family=\"sans-serif\">\n\
+        <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n{}</svg>\n",
+        largura_total * escala,
+        deslocamento_y * escala,
+        corpo
+    )
 }
 
 fn key_to_char(key: egui::Key) -> Option<char> {
@@ -1806,12 +6727,209 @@ fn main() -> eframe::Result<()> {
     // Verifica e aplica atualizações antes de iniciar o programa
     check_for_updates();
 
-    let mut native_options = eframe::NativeOptions::default();
-    native_options.initial_window_size = Some(egui::Vec2::new(1024.0, 768.0));
-    native_options.resizable = true;
+    let native_options = eframe::NativeOptions {
+        initial_window_size: Some(egui::Vec2::new(1024.0, 768.0)),
+        resizable: true,
+        ..Default::default()
+    };
     eframe::run_native(
         "Calculadora de Construção",
         native_options,
         Box::new(|_cc| Box::new(CalculadoraConstrucao::default())),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn por_tamanho(tamanhos: &[Decimal]) -> Vec<(Decimal, Decimal)> {
+        tamanhos.iter().map(|t| (*t, *t)).collect()
+    }
+
+    #[test]
+    fn combinar_embalagens_rejunte() {
+        let embalagens = por_tamanho(&EMBALAGENS_REJUNTE);
+        assert_eq!(combinar_embalagens(dec!(23), &embalagens), vec![3, 0, 1]);
+        assert_eq!(combinar_embalagens(dec!(17.2), &embalagens), vec![3, 3, 0]);
+        assert_eq!(combinar_embalagens(dec!(40), &embalagens), vec![0, 0, 2]);
+        assert_eq!(combinar_embalagens(Decimal::ZERO, &embalagens), vec![0, 0, 0]);
+        assert_eq!(combinar_embalagens(dec!(3000), &embalagens), vec![0, 0, 150]);
+    }
+
+    #[test]
+    fn combinar_embalagens_baldes() {
+        let embalagens = por_tamanho(&[dec!(18), dec!(3.6)]);
+        assert_eq!(combinar_embalagens(dec!(20), &embalagens), vec![1, 1]);
+        assert_eq!(combinar_embalagens(dec!(36), &embalagens), vec![2, 0]);
+        assert_eq!(combinar_embalagens(dec!(7), &embalagens), vec![0, 2]);
+
+        // O balde pequeno sai mais caro por litro, mas dois ainda custam menos que um grande
+        let precos = [(dec!(18), dec!(300)), (dec!(3.6), dec!(70))];
+        assert_eq!(combinar_embalagens(dec!(20), &precos), vec![1, 1]);
+        assert_eq!(combinar_embalagens(dec!(7), &precos), vec![0, 2]);
+        assert_eq!(combinar_embalagens(dec!(15), &precos), vec![1, 0]);
+    }
+
+    #[test]
+    fn combinar_embalagens_empates() {
+        // Mesmo custo: menos embalagens
+        let precos = [(dec!(1), dec!(10)), (dec!(5), dec!(50)), (dec!(20), dec!(200))];
+        assert_eq!(combinar_embalagens(dec!(20), &precos), vec![0, 0, 1]);
+
+        // Mesmo custo e mesmas embalagens: a que rende mais
+        let precos = [(dec!(1), dec!(10)), (dec!(5), dec!(10)), (dec!(20), dec!(200))];
+        assert_eq!(combinar_embalagens(dec!(1), &precos), vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn paginar_ambiente_multiplo_da_peca() {
+        let paginacao = CalculadoraPiso::paginar(
+            dec!(2.4),
+            dec!(3.0),
+            dec!(0.6),
+            dec!(0.6),
+            Decimal::ZERO,
+            PadraoAssentamento::Reto,
+            InicioPaginacao::Canto,
+        );
+        assert_eq!(paginacao.inteiras, 20);
+        assert_eq!(paginacao.cortadas, 0);
+        assert_eq!(paginacao.pecas_para_cortes, 0);
+        assert!(paginacao.sobras.is_empty());
+    }
+
+    #[test]
+    fn paginar_sobra_do_corte_serve_a_outra_peca() {
+        // 2,70 m: quatro peças inteiras e uma faixa de 0,30 m por fileira; cada peça
+        // aberta dá duas faixas
+        let paginacao = CalculadoraPiso::paginar(
+            dec!(2.4),
+            dec!(2.7),
+            dec!(0.6),
+            dec!(0.6),
+            Decimal::ZERO,
+            PadraoAssentamento::Reto,
+            InicioPaginacao::Canto,
+        );
+        assert_eq!(paginacao.inteiras, 16);
+        assert_eq!(paginacao.cortadas, 4);
+        assert_eq!(paginacao.pecas_para_cortes, 2);
+    }
+
+    #[test]
+    fn paginar_amarracao_meia_peca() {
+        // Fileiras alternadas deslocadas de meia peça: duas meias peças em cada uma
+        let paginacao = CalculadoraPiso::paginar(
+            dec!(2.4),
+            dec!(3.0),
+            dec!(0.6),
+            dec!(0.6),
+            Decimal::ZERO,
+            PadraoAssentamento::AmarracaoMeia,
+            InicioPaginacao::Canto,
+        );
+        assert_eq!(paginacao.inteiras, 18);
+        assert_eq!(paginacao.cortadas, 4);
+        assert_eq!(paginacao.pecas_para_cortes, 2);
+    }
+
+    #[test]
+    fn recortar_poligono_pelo_ambiente() {
+        let quadrado = vec![(dec!(0.5), dec!(0.5)), (dec!(1.5), dec!(0.5)), (dec!(1.5), dec!(1.5)), (dec!(0.5), dec!(1.5))];
+        assert_eq!(area_poligono(&recortar_poligono(quadrado, dec!(2), dec!(2))), dec!(1));
+
+        // Metade do quadrado fora da parede x = 0
+        let quadrado = vec![(dec!(-0.5), dec!(0)), (dec!(0.5), dec!(0)), (dec!(0.5), dec!(1)), (dec!(-0.5), dec!(1))];
+        assert_eq!(area_poligono(&recortar_poligono(quadrado, dec!(2), dec!(2))), dec!(0.5));
+
+        // Losango centrado no canto: sobra um quarto
+        let losango = vec![(dec!(1), dec!(0)), (dec!(0), dec!(1)), (dec!(-1), dec!(0)), (dec!(0), dec!(-1))];
+        assert_eq!(area_poligono(&recortar_poligono(losango, dec!(2), dec!(2))), dec!(0.5));
+
+        // Totalmente fora
+        let fora = vec![(dec!(3), dec!(3)), (dec!(4), dec!(3)), (dec!(4), dec!(4))];
+        assert!(recortar_poligono(fora, dec!(2), dec!(2)).is_empty());
+    }
+
+    #[test]
+    fn plano_de_corte_sem_sobra() {
+        let barras = CalculadoraLaje::plano_de_corte(&[dec!(4), dec!(4), dec!(4)], dec!(12), &[]);
+        assert_eq!(barras.len(), 1);
+        assert_eq!(barras[0].sobra(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn plano_de_corte_usa_sobras_de_obra() {
+        // A viga de 6 m abre uma barra; a de 4 m cabe melhor na sobra de 4,5 m
+        let barras = CalculadoraLaje::plano_de_corte(&[dec!(4), dec!(6)], dec!(12), &[dec!(4.5), dec!(2)]);
+        assert_eq!(barras.len(), 2);
+        let reaproveitada = barras.iter().find(|barra| barra.reaproveitada).unwrap();
+        assert_eq!(reaproveitada.cortes, vec![dec!(4)]);
+        assert_eq!(reaproveitada.sobra(), dec!(0.5));
+
+        let barras = CalculadoraLaje::plano_de_corte(&[dec!(5), dec!(5), dec!(5)], dec!(12), &[]);
+        assert_eq!(barras.len(), 2);
+        assert_eq!(barras.iter().map(|barra| barra.sobra()).sum::<Decimal>(), dec!(9));
+    }
+
+    #[test]
+    fn paginar_laminado_ambiente_curto_reaproveita_sobra() {
+        // Cada régua de 1200 mm dá duas peças de 500 mm
+        let paginacao = CalculadoraPiso::paginar_laminado(dec!(760), dec!(500), dec!(1200), dec!(190), dec!(300));
+        assert_eq!(paginacao.fileiras, 4);
+        assert!(!paginacao.bordas_equilibradas);
+        assert_eq!(paginacao.reguas, 2);
+        assert_eq!(paginacao.cortes_final, 4);
+    }
+
+    #[test]
+    fn paginar_laminado_sobras_entre_fileiras() {
+        // 3000 mm por fileira: sem reaproveitar seriam 3 réguas por fileira
+        let paginacao = CalculadoraPiso::paginar_laminado(dec!(1900), dec!(3000), dec!(1200), dec!(190), dec!(300));
+        assert_eq!(paginacao.fileiras, 10);
+        assert_eq!(paginacao.largura_borda, dec!(190));
+        assert!(paginacao.reguas < 30);
+        assert!(paginacao.reguas >= 25); // 10 x 3000 / 1200
+    }
+
+    #[test]
+    fn paginar_laminado_equilibra_borda_estreita() {
+        // 800 mm = 4 fileiras de 190 mm + 40 mm; a última e a primeira ficam com 115 mm
+        let paginacao = CalculadoraPiso::paginar_laminado(dec!(800), dec!(1000), dec!(1200), dec!(190), dec!(300));
+        assert_eq!(paginacao.fileiras, 5);
+        assert!(paginacao.bordas_equilibradas);
+        assert_eq!(paginacao.largura_borda, dec!(115));
+    }
+
+    #[test]
+    fn planejar_cortes_forro_sem_desperdicio() {
+        let tamanhos = [dec!(3), dec!(4), dec!(5), dec!(6)];
+        let plano = CalculadoraForro::planejar_cortes(&[(0, dec!(3), 5)], 1, &tamanhos);
+        assert_eq!(plano.pecas[0], [5, 0, 0, 0]);
+        assert_eq!(plano.juntas[0], 0);
+        assert_eq!(plano.desperdicio[0], Decimal::ZERO);
+    }
+
+    #[test]
+    fn planejar_cortes_forro_emenda_fileira_longa() {
+        // 7 m: régua de 6 m emendada com 1 m tirado de uma régua de 3 m
+        let tamanhos = [dec!(3), dec!(4), dec!(5), dec!(6)];
+        let plano = CalculadoraForro::planejar_cortes(&[(0, dec!(7), 1)], 1, &tamanhos);
+        assert_eq!(plano.pecas[0], [1, 0, 0, 1]);
+        assert_eq!(plano.juntas[0], 1);
+        assert_eq!(plano.desperdicio[0], dec!(2));
+    }
+
+    #[test]
+    fn planejar_cortes_forro_sobras_entre_ambientes() {
+        // As fileiras de 0,5 m do segundo ambiente saem das sobras do primeiro
+        let tamanhos = [dec!(3), dec!(4), dec!(5), dec!(6)];
+        let plano =
+            CalculadoraForro::planejar_cortes(&[(0, dec!(3.5), 2), (1, dec!(0.5), 2)], 2, &tamanhos);
+        assert_eq!(plano.pecas[0], [0, 2, 0, 0]);
+        assert_eq!(plano.pecas[1], [0, 0, 0, 0]);
+        assert_eq!(plano.reaproveitado[1], dec!(1));
+        assert_eq!(plano.desperdicio, vec![Decimal::ZERO, Decimal::ZERO]);
+    }
+}